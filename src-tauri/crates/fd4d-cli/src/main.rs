@@ -0,0 +1,418 @@
+//! Interface de linha de comando para os geradores e validadores de documentos, sem janela.
+//!
+//! Códigos de saída: 0 quando tudo foi gerado ou é válido, 1 quando algum documento é
+//! inválido e 2 em erro de uso ou de E/S.
+
+use fd4d_core::cpf_cnpj::{self, GeradorDocumentos};
+use fd4d_core::{documentos, inscricao_estadual};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+
+const USO: &str = "Uso: fd4d-cli <documento> <gen|validate> [opções] [valores...]
+
+Documentos: cpf, cnpj, pis, cnh, renavam, titulo, cns, ie
+
+gen:
+  --count N        quantidade de documentos (padrão 1)
+  --mask           aplica a máscara
+  --uf UF          UF do CPF/título; obrigatória para ie
+  --alnum          CNPJ alfanumérico
+  --seed N         semente para geração reproduzível
+
+validate:
+  valores como argumentos; sem valores (ou com \"-\"), lê um por linha da entrada padrão
+  --uf UF          obrigatória para ie
+
+Comuns:
+  --format FORMATO text (padrão), json ou csv
+
+Saída: 0 se tudo for válido, 1 se algum documento for inválido, 2 em erro de uso.";
+
+const DOCUMENTOS: [&str; 8] = [
+    "cpf", "cnpj", "pis", "cnh", "renavam", "titulo", "cns", "ie",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Formato {
+    Texto,
+    Json,
+    Csv,
+}
+
+struct Opcoes {
+    quantidade: usize,
+    com_mascara: bool,
+    uf: Option<String>,
+    alfanumerico: bool,
+    semente: Option<u64>,
+    formato: Formato,
+    valores: Vec<String>,
+}
+
+fn ler_opcoes(mut args: impl Iterator<Item = String>) -> Result<Opcoes, String> {
+    let mut opcoes = Opcoes {
+        quantidade: 1,
+        com_mascara: false,
+        uf: None,
+        alfanumerico: false,
+        semente: None,
+        formato: Formato::Texto,
+        valores: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        let mut valor = |nome: &str| {
+            args.next()
+                .ok_or_else(|| format!("{} precisa de um valor", nome))
+        };
+        match arg.as_str() {
+            "--count" => {
+                opcoes.quantidade = valor("--count")?
+                    .parse()
+                    .map_err(|_| "--count deve ser um número".to_string())?
+            }
+            "--mask" => opcoes.com_mascara = true,
+            "--uf" => opcoes.uf = Some(valor("--uf")?),
+            "--alnum" => opcoes.alfanumerico = true,
+            "--seed" => {
+                opcoes.semente = Some(
+                    valor("--seed")?
+                        .parse()
+                        .map_err(|_| "--seed deve ser um número".to_string())?,
+                )
+            }
+            "--format" => {
+                opcoes.formato = match valor("--format")?.as_str() {
+                    "text" => Formato::Texto,
+                    "json" => Formato::Json,
+                    "csv" => Formato::Csv,
+                    outro => return Err(format!("Formato desconhecido: {}", outro)),
+                }
+            }
+            opcao if opcao.starts_with("--") => {
+                return Err(format!("Opção desconhecida: {}", opcao))
+            }
+            _ => opcoes.valores.push(arg),
+        }
+    }
+
+    Ok(opcoes)
+}
+
+fn uf_obrigatoria(opcoes: &Opcoes) -> Result<&str, String> {
+    opcoes
+        .uf
+        .as_deref()
+        .ok_or_else(|| "Informe --uf para inscrição estadual".to_string())
+}
+
+/// Gera um documento a partir de `gerador`, que é semeado uma única vez para o lote todo.
+fn gerar(
+    documento: &str,
+    opcoes: &Opcoes,
+    gerador: &mut GeradorDocumentos,
+) -> Result<String, String> {
+    let uf = opcoes.uf.as_deref();
+    let semente = Some(gerador.semente_derivada());
+    match documento {
+        "cpf" => {
+            let regiao = uf.map(cpf_cnpj::regiao_fiscal_da_uf).transpose()?;
+            Ok(gerador.cpf(opcoes.com_mascara, regiao))
+        }
+        "cnpj" => Ok(gerador.cnpj(opcoes.com_mascara, opcoes.alfanumerico)),
+        "pis" => Ok(documentos::gerar_pis(opcoes.com_mascara, semente)),
+        "cnh" => Ok(documentos::gerar_cnh(semente)),
+        "renavam" => Ok(documentos::gerar_renavam(semente)),
+        "titulo" => documentos::gerar_titulo_eleitor(opcoes.com_mascara, uf, semente),
+        "cns" => Ok(documentos::gerar_cns(opcoes.com_mascara, None, semente)),
+        "ie" => inscricao_estadual::gerar_inscricao_estadual(
+            uf_obrigatoria(opcoes)?,
+            opcoes.com_mascara,
+            None,
+            semente,
+        ),
+        _ => Err(format!("Documento desconhecido: {}", documento)),
+    }
+}
+
+fn validar(documento: &str, opcoes: &Opcoes, valor: &str) -> Result<Value, String> {
+    let relatorio = match documento {
+        "cpf" => serde_json::to_value(cpf_cnpj::validar_cpf(valor)),
+        "cnpj" => serde_json::to_value(cpf_cnpj::validar_cnpj(valor)),
+        "pis" => serde_json::to_value(documentos::validar_pis(valor)),
+        "cnh" => serde_json::to_value(documentos::validar_cnh(valor)),
+        "renavam" => serde_json::to_value(documentos::validar_renavam(valor)),
+        "titulo" => serde_json::to_value(documentos::validar_titulo_eleitor(valor)),
+        "cns" => serde_json::to_value(documentos::validar_cns(valor)),
+        "ie" => serde_json::to_value(inscricao_estadual::validar_inscricao_estadual(
+            uf_obrigatoria(opcoes)?,
+            valor,
+        )?),
+        _ => return Err(format!("Documento desconhecido: {}", documento)),
+    };
+    relatorio.map_err(|e| e.to_string())
+}
+
+fn campo_csv(valor: &str) -> String {
+    if valor.contains([',', '"', '\n']) {
+        format!("\"{}\"", valor.replace('"', "\"\""))
+    } else {
+        valor.to_string()
+    }
+}
+
+/// Escreve cada documento assim que é gerado; o JSON sai como um array numa linha só.
+fn executar_gen(documento: &str, opcoes: &Opcoes, saida: &mut impl Write) -> Result<bool, String> {
+    let io_err = |e: io::Error| e.to_string();
+    let mut gerador = GeradorDocumentos::new(opcoes.semente);
+
+    match opcoes.formato {
+        Formato::Csv => writeln!(saida, "{}", documento).map_err(io_err)?,
+        Formato::Json => write!(saida, "[").map_err(io_err)?,
+        Formato::Texto => {}
+    }
+
+    for i in 0..opcoes.quantidade {
+        let valor = gerar(documento, opcoes, &mut gerador)?;
+        match opcoes.formato {
+            Formato::Texto => writeln!(saida, "{}", valor),
+            Formato::Json => {
+                let separador = if i == 0 { "" } else { "," };
+                write!(saida, "{}{}", separador, json!(valor))
+            }
+            Formato::Csv => writeln!(saida, "{}", campo_csv(&valor)),
+        }
+        .map_err(io_err)?;
+    }
+
+    if opcoes.formato == Formato::Json {
+        writeln!(saida, "]").map_err(io_err)?;
+    }
+
+    Ok(true)
+}
+
+/// Valida os valores dos argumentos ou, sem eles, a entrada padrão linha a linha. Lendo da
+/// entrada padrão, cada resultado é enviado assim que sai, para acompanhar um pipe.
+fn executar_validate(
+    documento: &str,
+    opcoes: &Opcoes,
+    saida: &mut impl Write,
+) -> Result<bool, String> {
+    let io_err = |e: io::Error| e.to_string();
+
+    let da_entrada = opcoes.valores.is_empty() || opcoes.valores == ["-"];
+    let valores: Box<dyn Iterator<Item = io::Result<String>>> = if da_entrada {
+        Box::new(io::stdin().lock().lines())
+    } else {
+        Box::new(opcoes.valores.iter().cloned().map(Ok))
+    };
+
+    let mut todos_validos = true;
+    let mut primeiro = true;
+
+    match opcoes.formato {
+        Formato::Csv => writeln!(saida, "valor,valido,falha").map_err(io_err)?,
+        Formato::Json => write!(saida, "[").map_err(io_err)?,
+        Formato::Texto => {}
+    }
+
+    for valor in valores {
+        let valor = valor.map_err(io_err)?;
+        if da_entrada && valor.trim().is_empty() {
+            continue;
+        }
+        let relatorio = validar(documento, opcoes, valor.trim())?;
+        let valido = relatorio["valid"].as_bool().unwrap_or(false);
+        let falha = relatorio["failure"].as_str().unwrap_or("").to_string();
+        todos_validos &= valido;
+
+        match opcoes.formato {
+            Formato::Texto => {
+                if valido {
+                    writeln!(saida, "{}\tválido", valor)
+                } else {
+                    writeln!(saida, "{}\tinválido ({})", valor, falha)
+                }
+            }
+            Formato::Csv => writeln!(saida, "{},{},{}", campo_csv(&valor), valido, falha),
+            Formato::Json => {
+                let separador = if primeiro { "" } else { "," };
+                write!(
+                    saida,
+                    "{}{}",
+                    separador,
+                    json!({ "valor": valor, "relatorio": relatorio })
+                )
+            }
+        }
+        .map_err(io_err)?;
+        primeiro = false;
+
+        if da_entrada {
+            saida.flush().map_err(io_err)?;
+        }
+    }
+
+    if opcoes.formato == Formato::Json {
+        writeln!(saida, "]").map_err(io_err)?;
+    }
+
+    Ok(todos_validos)
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let (documento, acao) = match (args.next(), args.next()) {
+        (Some(documento), Some(acao)) => (documento.to_ascii_lowercase(), acao),
+        (Some(ajuda), None) if ajuda == "--help" || ajuda == "-h" => {
+            println!("{}", USO);
+            return ExitCode::SUCCESS;
+        }
+        _ => {
+            eprintln!("{}", USO);
+            return ExitCode::from(2);
+        }
+    };
+
+    if !DOCUMENTOS.contains(&documento.as_str()) {
+        eprintln!("Documento desconhecido: {}\n\n{}", documento, USO);
+        return ExitCode::from(2);
+    }
+
+    let opcoes = match ler_opcoes(args) {
+        Ok(opcoes) => opcoes,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USO);
+            return ExitCode::from(2);
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut saida = BufWriter::new(stdout.lock());
+
+    let resultado = match acao.as_str() {
+        "gen" => executar_gen(&documento, &opcoes, &mut saida),
+        "validate" => executar_validate(&documento, &opcoes, &mut saida),
+        outra => Err(format!("Ação desconhecida: {}\n\n{}", outra, USO)),
+    }
+    .and_then(|ok| saida.flush().map(|_| ok).map_err(|e| e.to_string()));
+
+    match resultado {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opcoes(args: &[&str]) -> Opcoes {
+        ler_opcoes(args.iter().map(|arg| arg.to_string())).unwrap()
+    }
+
+    fn gerar_linhas(documento: &str, args: &[&str]) -> Vec<String> {
+        let mut saida = Vec::new();
+        executar_gen(documento, &opcoes(args), &mut saida).unwrap();
+        String::from_utf8(saida)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn le_opcoes_e_rejeita_as_invalidas() {
+        let lidas = opcoes(&["--count", "3", "--mask", "--uf", "SP", "--seed", "7", "x"]);
+        assert_eq!(lidas.quantidade, 3);
+        assert!(lidas.com_mascara);
+        assert_eq!(lidas.uf.as_deref(), Some("SP"));
+        assert_eq!(lidas.semente, Some(7));
+        assert_eq!(lidas.valores, ["x"]);
+
+        let args = |args: &[&str]| ler_opcoes(args.iter().map(|arg| arg.to_string()));
+        assert!(args(&["--count", "muitos"]).is_err());
+        assert!(args(&["--seed"]).is_err());
+        assert!(args(&["--format", "xml"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+    }
+
+    #[test]
+    fn semente_reproduz_o_lote_sem_sobrepor_sementes_vizinhas() {
+        for documento in DOCUMENTOS.iter().filter(|&&documento| documento != "ie") {
+            let lote = gerar_linhas(documento, &["--count", "5", "--seed", "1"]);
+            assert_eq!(lote.len(), 5);
+            assert_eq!(
+                lote,
+                gerar_linhas(documento, &["--count", "5", "--seed", "1"])
+            );
+
+            let vizinho = gerar_linhas(documento, &["--count", "5", "--seed", "2"]);
+            assert!(
+                lote[1..].iter().all(|valor| !vizinho.contains(valor)),
+                "{}",
+                documento
+            );
+        }
+    }
+
+    #[test]
+    fn documentos_gerados_passam_na_validacao() {
+        for documento in DOCUMENTOS {
+            let args = ["--count", "20", "--mask", "--uf", "GO", "--seed", "3"];
+            let valores = gerar_linhas(documento, &args);
+            let mut opcoes = opcoes(&args);
+            opcoes.valores = valores;
+            let mut saida = Vec::new();
+            assert_eq!(
+                executar_validate(documento, &opcoes, &mut saida),
+                Ok(true),
+                "{}",
+                String::from_utf8_lossy(&saida)
+            );
+        }
+    }
+
+    #[test]
+    fn validate_indica_a_falha_em_csv() {
+        let mut opcoes = opcoes(&["--format", "csv"]);
+        opcoes.valores = vec!["529.982.247-25".into(), "529.982.247-24".into()];
+        let mut saida = Vec::new();
+        assert_eq!(executar_validate("cpf", &opcoes, &mut saida), Ok(false));
+        assert_eq!(
+            String::from_utf8(saida).unwrap(),
+            "valor,valido,falha\n529.982.247-25,true,\n529.982.247-24,false,secondCheckDigitMismatch\n"
+        );
+        assert!(gerar("ie", &opcoes, &mut GeradorDocumentos::new(None)).is_err());
+    }
+
+    #[test]
+    fn json_continua_sendo_um_array() {
+        let lote = gerar_linhas("cpf", &["--count", "3", "--seed", "1", "--format", "json"]);
+        assert_eq!(lote.len(), 1);
+        let valores: Vec<String> = serde_json::from_str(&lote[0]).unwrap();
+        assert_eq!(
+            valores,
+            gerar_linhas("cpf", &["--count", "3", "--seed", "1"])
+        );
+        assert_eq!(
+            gerar_linhas("cpf", &["--count", "0", "--format", "json"]),
+            ["[]"]
+        );
+
+        let mut opcoes = opcoes(&["--format", "json"]);
+        opcoes.valores = vec!["529.982.247-25".into(), "1".into()];
+        let mut saida = Vec::new();
+        assert_eq!(executar_validate("cpf", &opcoes, &mut saida), Ok(false));
+        let relatorios: Vec<Value> = serde_json::from_slice(&saida).unwrap();
+        assert_eq!(relatorios.len(), 2);
+        assert_eq!(relatorios[0]["relatorio"]["valid"], true);
+        assert_eq!(relatorios[1]["relatorio"]["failure"], "wrongLength");
+    }
+}
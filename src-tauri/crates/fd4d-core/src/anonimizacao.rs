@@ -0,0 +1,216 @@
+use crate::cpf_cnpj::{GeradorDocumentos, TipoDocumento};
+use crate::extracao::{encontrar_documentos, DocumentoEncontrado};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Quantidade de caracteres hexadecimais mantidos do SHA-256 no modo `hash`.
+const TAMANHO_HASH: usize = 16;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum ModoAnonimizacao {
+    /// Mantém só os dígitos centrais: `***.456.789-**`.
+    Mascara,
+    /// Substitui por `sha256:` + prefixo do hash do documento com o sal.
+    Hash,
+    /// Substitui por outro documento válido, no mesmo formato do original.
+    Pseudonimo,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultadoAnonimizacao {
+    pub texto: String,
+    pub substituidos: usize,
+    pub ignorados: usize,
+}
+
+/// Posições (entre os caracteres alfanuméricos) que continuam visíveis no modo máscara.
+fn posicoes_visiveis(tipo: TipoDocumento) -> std::ops::Range<usize> {
+    match tipo {
+        TipoDocumento::Cpf => 3..9,
+        TipoDocumento::Cnpj => 2..8,
+    }
+}
+
+fn mascarar(documento: &DocumentoEncontrado) -> String {
+    let visiveis = posicoes_visiveis(documento.tipo);
+    let mut posicao = 0;
+    documento
+        .valor
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_alphanumeric() {
+                return c;
+            }
+            let visivel = visiveis.contains(&posicao);
+            posicao += 1;
+            if visivel {
+                c
+            } else {
+                '*'
+            }
+        })
+        .collect()
+}
+
+fn resumo_sha256(sal: &str, documento: &DocumentoEncontrado) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(sal.as_bytes());
+    hasher.update(documento.validacao.normalized.as_bytes());
+    hasher.finalize().into()
+}
+
+fn hash(sal: &str, documento: &DocumentoEncontrado) -> String {
+    let hex: String = resumo_sha256(sal, documento)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("sha256:{}", &hex[..TAMANHO_HASH])
+}
+
+/// O hash com sal vira a semente do gerador, então o pseudônimo é determinístico por sessão
+/// sem precisar guardar uma tabela de correspondência. CNPJs de filial continuam filiais.
+fn pseudonimo(sal: &str, documento: &DocumentoEncontrado) -> String {
+    let resumo = resumo_sha256(sal, documento);
+    let semente = u64::from_be_bytes(resumo[..8].try_into().unwrap());
+    let mut gerador = GeradorDocumentos::new(Some(semente));
+
+    let com_mascara = documento.validacao.was_masked;
+    match documento.tipo {
+        TipoDocumento::Cpf => gerador.cpf(com_mascara, None),
+        TipoDocumento::Cnpj => {
+            let normalizado = &documento.validacao.normalized;
+            let alfanumerico = normalizado.chars().any(|c| c.is_ascii_alphabetic());
+            let matriz = normalizado.get(8..12) == Some("0001");
+            gerador.cnpj_matriz_ou_filial(com_mascara, alfanumerico, matriz)
+        }
+    }
+}
+
+/// Substitui os CPFs/CNPJs de `texto` conforme o `modo`. O mesmo `sal` gera sempre o mesmo
+/// hash/pseudônimo; com `apenas_validos`, trechos com dígito verificador errado são mantidos.
+pub fn anonimizar_texto(
+    texto: &str,
+    modo: ModoAnonimizacao,
+    sal: &str,
+    apenas_validos: bool,
+) -> ResultadoAnonimizacao {
+    let chars: Vec<char> = texto.chars().collect();
+    let mut saida = String::with_capacity(texto.len());
+    let mut substituidos = 0;
+    let mut ignorados = 0;
+    let mut cursor = 0;
+
+    for documento in encontrar_documentos(texto) {
+        if apenas_validos && !documento.validacao.valid {
+            ignorados += 1;
+            continue;
+        }
+
+        saida.extend(&chars[cursor..documento.inicio]);
+        saida.push_str(&match modo {
+            ModoAnonimizacao::Mascara => mascarar(&documento),
+            ModoAnonimizacao::Hash => hash(sal, &documento),
+            ModoAnonimizacao::Pseudonimo => pseudonimo(sal, &documento),
+        });
+        cursor = documento.fim;
+        substituidos += 1;
+    }
+    saida.extend(&chars[cursor..]);
+
+    ResultadoAnonimizacao {
+        texto: saida,
+        substituidos,
+        ignorados,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpf_cnpj::{validar_cnpj, validar_cpf};
+
+    const TEXTO: &str = "CPF 529.982.247-25, CNPJ 11222333000181, alfa 12ABC34501DE35.";
+
+    #[test]
+    fn mascara_mantem_so_os_digitos_centrais() {
+        let resultado = anonimizar_texto(TEXTO, ModoAnonimizacao::Mascara, "", true);
+        assert_eq!(
+            resultado.texto,
+            "CPF ***.982.247-**, CNPJ **222333******, alfa **ABC345******."
+        );
+        assert_eq!(resultado.substituidos, 3);
+    }
+
+    #[test]
+    fn hash_depende_do_sal() {
+        let a = anonimizar_texto(TEXTO, ModoAnonimizacao::Hash, "sal", true).texto;
+        let b = anonimizar_texto(TEXTO, ModoAnonimizacao::Hash, "sal", true).texto;
+        let c = anonimizar_texto(TEXTO, ModoAnonimizacao::Hash, "outro", true).texto;
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(!a.contains("529.982.247-25"));
+        assert_eq!(a.matches("sha256:").count(), 3);
+    }
+
+    #[test]
+    fn pseudonimo_e_valido_e_mantem_o_formato() {
+        let resultado = anonimizar_texto(TEXTO, ModoAnonimizacao::Pseudonimo, "sal", true);
+        let documentos = encontrar_documentos(&resultado.texto);
+        assert_eq!(documentos.len(), 3);
+        assert!(validar_cpf(&documentos[0].valor).valid);
+        assert!(documentos[0].validacao.was_masked);
+        assert!(validar_cnpj(&documentos[1].valor).valid);
+        assert!(!documentos[1].validacao.was_masked);
+        assert!(validar_cnpj(&documentos[2].valor).valid);
+        assert_ne!(documentos[0].valor, "529.982.247-25");
+        assert_eq!(
+            resultado.texto,
+            anonimizar_texto(TEXTO, ModoAnonimizacao::Pseudonimo, "sal", true).texto
+        );
+    }
+
+    #[test]
+    fn pseudonimo_mantem_matriz_e_filial() {
+        let texto = "11.222.333/0001-81 11.222.333/0002-62 12ABC34501DE35";
+        for sal in ["a", "b", "c", "d"] {
+            let resultado = anonimizar_texto(texto, ModoAnonimizacao::Pseudonimo, sal, true);
+            let documentos = encontrar_documentos(&resultado.texto);
+            assert_eq!(documentos.len(), 3, "{}", resultado.texto);
+            assert!(documentos.iter().all(|d| d.validacao.valid));
+            let ordens: Vec<&str> = documentos
+                .iter()
+                .map(|d| &d.validacao.normalized[8..12])
+                .collect();
+            assert_eq!(ordens[0], "0001");
+            assert_ne!(ordens[1], "0001");
+            assert_ne!(ordens[2], "0001");
+        }
+    }
+
+    #[test]
+    fn preserva_identificadores_que_nao_sao_documentos() {
+        let texto = "SKU ABCDEFGHIJKL12 pedido PED2024XYZ9A77";
+        for modo in [
+            ModoAnonimizacao::Mascara,
+            ModoAnonimizacao::Hash,
+            ModoAnonimizacao::Pseudonimo,
+        ] {
+            let resultado = anonimizar_texto(texto, modo, "sal", false);
+            assert_eq!(resultado.texto, texto);
+            assert_eq!(resultado.substituidos, 0);
+        }
+    }
+
+    #[test]
+    fn apenas_validos_preserva_documentos_invalidos() {
+        let texto = "529.982.247-24 e 529.982.247-25";
+        let resultado = anonimizar_texto(texto, ModoAnonimizacao::Mascara, "", true);
+        assert_eq!(resultado.texto, "529.982.247-24 e ***.982.247-**");
+        assert_eq!((resultado.substituidos, resultado.ignorados), (1, 1));
+
+        let resultado = anonimizar_texto(texto, ModoAnonimizacao::Mascara, "", false);
+        assert_eq!(resultado.substituidos, 2);
+    }
+}
@@ -0,0 +1,654 @@
+use crate::cpf_cnpj::{aplicar_mascara, GeradorDocumentos};
+use crate::validacao::{ValidationFailure, ValidationReport};
+use chrono::{Duration, Local, NaiveDate};
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+const MASCARA_LINHA_BANCARIA: &str = "#####.##### #####.###### #####.###### # ##############";
+const MASCARA_LINHA_ARRECADACAO: &str = "###########-# ###########-# ###########-# ###########-#";
+
+/// Códigos de compensação dos bancos mais comuns, usados no sorteio e na decodificação.
+const BANCOS: [(&str, &str); 15] = [
+    ("001", "Banco do Brasil"),
+    ("004", "Banco do Nordeste"),
+    ("033", "Santander"),
+    ("041", "Banrisul"),
+    ("070", "BRB"),
+    ("077", "Banco Inter"),
+    ("104", "Caixa Econômica Federal"),
+    ("208", "BTG Pactual"),
+    ("237", "Bradesco"),
+    ("260", "Nu Pagamentos"),
+    ("336", "C6 Bank"),
+    ("341", "Itaú Unibanco"),
+    ("422", "Safra"),
+    ("748", "Sicredi"),
+    ("756", "Sicoob"),
+];
+
+/// Único código de moeda em uso: real.
+const MOEDA_REAL: u32 = 9;
+
+/// Produto dos boletos de arrecadação (contas de consumo, tributos, multas).
+const PRODUTO_ARRECADACAO: u32 = 8;
+
+/// Identificadores de valor da arrecadação com valor efetivo em reais (módulo 10 e 11).
+const IDENTIFICADORES_VALOR_EFETIVO: [u32; 2] = [6, 8];
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TipoBoleto {
+    /// Boleto de cobrança bancária: linha digitável de 47 dígitos.
+    Bancario,
+    /// Conta de concessionária ou tributo: linha digitável de 48 dígitos, começa com 8.
+    Arrecadacao,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BoletoDecodificado {
+    pub tipo: TipoBoleto,
+    pub codigo_barras: String,
+    pub linha_digitavel: String,
+    pub banco: Option<String>,
+    pub nome_banco: Option<String>,
+    pub fator_vencimento: Option<u32>,
+    /// Data no formato `AAAA-MM-DD`.
+    pub vencimento: Option<String>,
+    /// Ausente quando o boleto não traz valor ou, na arrecadação, traz um valor de referência.
+    pub valor_centavos: Option<u64>,
+    pub segmento: Option<u32>,
+    pub nome_segmento: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidacaoBoleto {
+    #[serde(flatten)]
+    pub relatorio: ValidationReport,
+    /// Bloco da linha digitável (a partir de 1) cujo dígito verificador não confere.
+    pub campo: Option<usize>,
+    pub boleto: Option<BoletoDecodificado>,
+}
+
+fn para_string(digits: &[u32]) -> String {
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+fn para_numero(digits: &[u32]) -> u64 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d as u64)
+}
+
+fn digitos(valor: &str) -> Vec<u32> {
+    valor.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+fn sortear_digitos<R: Rng + ?Sized>(rng: &mut R, quantidade: usize) -> Vec<u32> {
+    (0..quantidade).map(|_| rng.random_range(0..10)).collect()
+}
+
+/// Pesos 2 e 1 alternados da direita para a esquerda, somando os algarismos de cada produto.
+fn modulo_10(digits: &[u32]) -> u32 {
+    let soma: u32 = digits
+        .iter()
+        .rev()
+        .zip([2, 1].iter().cycle())
+        .map(|(d, peso)| {
+            let produto = d * peso;
+            produto / 10 + produto % 10
+        })
+        .sum();
+    (10 - soma % 10) % 10
+}
+
+/// Pesos de 2 a 9 repetidos da direita para a esquerda.
+fn resto_modulo_11(digits: &[u32]) -> u32 {
+    let soma: u32 = digits
+        .iter()
+        .rev()
+        .zip((2..=9).cycle())
+        .map(|(d, peso)| d * peso)
+        .sum();
+    soma % 11
+}
+
+/// DV geral do boleto bancário: resultados 10 e 11 viram 1.
+fn dv_bancario(digits: &[u32]) -> u32 {
+    match 11 - resto_modulo_11(digits) {
+        10 | 11 => 1,
+        dv => dv,
+    }
+}
+
+/// Módulo 11 da arrecadação: restos 0 e 1 viram 0.
+fn modulo_11_arrecadacao(digits: &[u32]) -> u32 {
+    match resto_modulo_11(digits) {
+        0 | 1 => 0,
+        resto => 11 - resto,
+    }
+}
+
+/// O 3º dígito da arrecadação define o módulo de todos os DVs: 6 e 7 usam módulo 10,
+/// 8 e 9 usam módulo 11.
+fn modulo_arrecadacao(identificador: u32) -> Option<fn(&[u32]) -> u32> {
+    match identificador {
+        6 | 7 => Some(modulo_10),
+        8 | 9 => Some(modulo_11_arrecadacao),
+        _ => None,
+    }
+}
+
+fn nome_banco(banco: &str) -> Option<&'static str> {
+    BANCOS
+        .iter()
+        .find(|(codigo, _)| *codigo == banco)
+        .map(|(_, nome)| *nome)
+}
+
+fn nome_segmento(segmento: u32) -> Option<&'static str> {
+    match segmento {
+        1 => Some("Prefeituras"),
+        2 => Some("Saneamento"),
+        3 => Some("Energia elétrica e gás"),
+        4 => Some("Telecomunicações"),
+        5 => Some("Órgãos governamentais"),
+        6 => Some("Carnês e assemelhados"),
+        7 => Some("Multas de trânsito"),
+        9 => Some("Uso exclusivo do banco"),
+        _ => None,
+    }
+}
+
+fn data_base() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1997, 10, 7).unwrap()
+}
+
+/// Fator de vencimento: dias desde 07/10/1997. Ao passar de 9999 volta para 1000
+/// (a primeira vez em 22/02/2025).
+pub fn fator_vencimento(data: NaiveDate) -> Result<u32, String> {
+    let dias = (data - data_base()).num_days();
+    if dias < 1000 {
+        return Err("Vencimento deve ser a partir de 03/07/2000".into());
+    }
+    Ok(((dias - 1000) % 9000 + 1000) as u32)
+}
+
+/// Data de um fator de vencimento. Como o fator se repete a cada 9000 dias, escolhe o
+/// ciclo mais próximo de `referencia`.
+pub fn data_do_fator(fator: u32, referencia: NaiveDate) -> NaiveDate {
+    let mut data = data_base() + Duration::days(fator as i64);
+    if fator < 1000 {
+        return data;
+    }
+
+    loop {
+        let proxima = data + Duration::days(9000);
+        if (proxima - referencia).num_days().abs() < (data - referencia).num_days().abs() {
+            data = proxima;
+        } else {
+            return data;
+        }
+    }
+}
+
+/// Linha digitável bancária: três campos com DV em módulo 10, o DV geral e fator + valor.
+fn linha_bancaria(barras: &[u32]) -> Vec<u32> {
+    let campos = [
+        [&barras[0..4], &barras[19..24]].concat(),
+        barras[24..34].to_vec(),
+        barras[34..44].to_vec(),
+    ];
+
+    let mut linha = Vec::with_capacity(47);
+    for campo in campos {
+        let dv = modulo_10(&campo);
+        linha.extend(campo);
+        linha.push(dv);
+    }
+    linha.push(barras[4]);
+    linha.extend(&barras[5..19]);
+    linha
+}
+
+fn barras_da_linha_bancaria(linha: &[u32]) -> Vec<u32> {
+    [
+        &linha[0..4],
+        &linha[32..47],
+        &linha[4..9],
+        &linha[10..20],
+        &linha[21..31],
+    ]
+    .concat()
+}
+
+/// Linha digitável da arrecadação: o código de barras em quatro blocos de 11 dígitos,
+/// cada um seguido do seu DV.
+fn linha_arrecadacao(barras: &[u32], modulo: fn(&[u32]) -> u32) -> Vec<u32> {
+    barras
+        .chunks(11)
+        .flat_map(|bloco| {
+            let mut bloco = bloco.to_vec();
+            bloco.push(modulo(&bloco));
+            bloco
+        })
+        .collect()
+}
+
+fn barras_da_linha_arrecadacao(linha: &[u32]) -> Vec<u32> {
+    linha
+        .chunks(12)
+        .flat_map(|bloco| bloco[..11].to_vec())
+        .collect()
+}
+
+/// Dígitos que entram no DV geral: todos menos a posição do próprio DV.
+fn sem_posicao(barras: &[u32], posicao: usize) -> Vec<u32> {
+    [&barras[..posicao], &barras[posicao + 1..]].concat()
+}
+
+fn decodificar(tipo: TipoBoleto, barras: &[u32]) -> BoletoDecodificado {
+    match tipo {
+        TipoBoleto::Bancario => {
+            let banco = para_string(&barras[0..3]);
+            let fator = para_numero(&barras[5..9]) as u32;
+            let valor = para_numero(&barras[9..19]);
+            let hoje = Local::now().date_naive();
+
+            BoletoDecodificado {
+                tipo,
+                codigo_barras: para_string(barras),
+                linha_digitavel: aplicar_mascara(
+                    MASCARA_LINHA_BANCARIA,
+                    &para_string(&linha_bancaria(barras)),
+                ),
+                nome_banco: nome_banco(&banco).map(String::from),
+                banco: Some(banco),
+                fator_vencimento: (fator > 0).then_some(fator),
+                vencimento: (fator > 0)
+                    .then(|| data_do_fator(fator, hoje).format("%Y-%m-%d").to_string()),
+                valor_centavos: (valor > 0).then_some(valor),
+                segmento: None,
+                nome_segmento: None,
+            }
+        }
+        TipoBoleto::Arrecadacao => {
+            let modulo = modulo_arrecadacao(barras[2]).unwrap();
+            let valor_efetivo = IDENTIFICADORES_VALOR_EFETIVO.contains(&barras[2]);
+
+            BoletoDecodificado {
+                tipo,
+                codigo_barras: para_string(barras),
+                linha_digitavel: aplicar_mascara(
+                    MASCARA_LINHA_ARRECADACAO,
+                    &para_string(&linha_arrecadacao(barras, modulo)),
+                ),
+                banco: None,
+                nome_banco: None,
+                fator_vencimento: None,
+                vencimento: None,
+                valor_centavos: valor_efetivo.then(|| para_numero(&barras[4..15])),
+                segmento: Some(barras[1]),
+                nome_segmento: nome_segmento(barras[1]).map(String::from),
+            }
+        }
+    }
+}
+
+fn falha_campo(
+    mut validacao: ValidacaoBoleto,
+    campo: usize,
+    esperado: u32,
+    informado: u32,
+) -> ValidacaoBoleto {
+    validacao.relatorio.expected_check_digits = Some(esperado.to_string());
+    validacao.relatorio.supplied_check_digits = Some(informado.to_string());
+    validacao.relatorio = validacao
+        .relatorio
+        .fail(ValidationFailure::FieldCheckDigitMismatch);
+    validacao.campo = Some(campo);
+    validacao
+}
+
+fn falha_dv_geral(
+    mut validacao: ValidacaoBoleto,
+    esperado: u32,
+    informado: u32,
+) -> ValidacaoBoleto {
+    validacao.relatorio.expected_check_digits = Some(esperado.to_string());
+    validacao.relatorio.supplied_check_digits = Some(informado.to_string());
+    validacao.relatorio = validacao
+        .relatorio
+        .fail(ValidationFailure::ChecksumMismatch);
+    validacao
+}
+
+fn falhar(mut validacao: ValidacaoBoleto, falha: ValidationFailure) -> ValidacaoBoleto {
+    validacao.relatorio = validacao.relatorio.fail(falha);
+    validacao
+}
+
+/// Valida um código de barras (44 dígitos) ou linha digitável (47 bancária, 48 arrecadação).
+/// Pontos e espaços são ignorados; se passar, devolve também o boleto decodificado.
+pub fn validar_boleto(boleto_input: &str) -> ValidacaoBoleto {
+    let normalized: String = boleto_input
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect();
+    let digits = digitos(&normalized);
+    let validacao = ValidacaoBoleto {
+        relatorio: ValidationReport::new(boleto_input, normalized),
+        campo: None,
+        boleto: None,
+    };
+
+    if boleto_input.chars().any(|c| c.is_ascii_alphabetic()) {
+        return falhar(validacao, ValidationFailure::InvalidCharacters);
+    }
+
+    let tipo = match (digits.len(), digits.first()) {
+        (44 | 48, Some(&PRODUTO_ARRECADACAO)) => TipoBoleto::Arrecadacao,
+        (44 | 47, Some(_)) => TipoBoleto::Bancario,
+        (48, Some(_)) => return falhar(validacao, ValidationFailure::InvalidPrefix),
+        _ => return falhar(validacao, ValidationFailure::WrongLength),
+    };
+    let eh_linha = digits.len() != 44;
+
+    match tipo {
+        TipoBoleto::Bancario => {
+            if digits[3] != MOEDA_REAL {
+                return falhar(validacao, ValidationFailure::InvalidPrefix);
+            }
+
+            let barras = if eh_linha {
+                for (campo, (inicio, dv)) in [(0, 9), (10, 20), (21, 31)].into_iter().enumerate() {
+                    let esperado = modulo_10(&digits[inicio..dv]);
+                    if esperado != digits[dv] {
+                        return falha_campo(validacao, campo + 1, esperado, digits[dv]);
+                    }
+                }
+                barras_da_linha_bancaria(&digits)
+            } else {
+                digits
+            };
+
+            let esperado = dv_bancario(&sem_posicao(&barras, 4));
+            if esperado != barras[4] {
+                return falha_dv_geral(validacao, esperado, barras[4]);
+            }
+
+            aprovar(validacao, tipo, &barras)
+        }
+        TipoBoleto::Arrecadacao => {
+            let Some(modulo) = modulo_arrecadacao(digits[2]) else {
+                return falhar(validacao, ValidationFailure::InvalidPrefix);
+            };
+            if nome_segmento(digits[1]).is_none() {
+                return falhar(validacao, ValidationFailure::InvalidPrefix);
+            }
+
+            let barras = if eh_linha {
+                for (campo, bloco) in digits.chunks(12).enumerate() {
+                    let esperado = modulo(&bloco[..11]);
+                    if esperado != bloco[11] {
+                        return falha_campo(validacao, campo + 1, esperado, bloco[11]);
+                    }
+                }
+                barras_da_linha_arrecadacao(&digits)
+            } else {
+                digits
+            };
+
+            let esperado = modulo(&sem_posicao(&barras, 3));
+            if esperado != barras[3] {
+                return falha_dv_geral(validacao, esperado, barras[3]);
+            }
+
+            aprovar(validacao, tipo, &barras)
+        }
+    }
+}
+
+fn aprovar(mut validacao: ValidacaoBoleto, tipo: TipoBoleto, barras: &[u32]) -> ValidacaoBoleto {
+    let boleto = decodificar(tipo, barras);
+    validacao.relatorio.masked = Some(boleto.linha_digitavel.clone());
+    validacao.relatorio = validacao.relatorio.pass();
+    validacao.boleto = Some(boleto);
+    validacao
+}
+
+/// Converte a linha digitável em código de barras e vice-versa.
+pub fn converter_boleto(boleto_input: &str) -> Result<String, String> {
+    let validacao = validar_boleto(boleto_input);
+    let eh_codigo_barras = validacao.relatorio.normalized.len() == 44;
+
+    match validacao.boleto {
+        Some(boleto) if eh_codigo_barras => Ok(boleto.linha_digitavel),
+        Some(boleto) => Ok(boleto.codigo_barras),
+        None => Err(match validacao.campo {
+            Some(campo) => format!("Boleto inválido: dígito verificador do campo {}", campo),
+            None => "Boleto inválido".into(),
+        }),
+    }
+}
+
+/// Gera um boleto válido. No bancário, `banco`, `vencimento` (`AAAA-MM-DD`) e o valor são
+/// sorteados se omitidos; na arrecadação, o `segmento` e o valor.
+pub fn gerar_boleto(
+    tipo: TipoBoleto,
+    banco: Option<&str>,
+    vencimento: Option<&str>,
+    valor_centavos: Option<u64>,
+    segmento: Option<u32>,
+    semente: Option<u64>,
+) -> Result<BoletoDecodificado, String> {
+    let mut gerador = GeradorDocumentos::new(semente);
+    let rng = gerador.rng();
+
+    let barras = match tipo {
+        TipoBoleto::Bancario => {
+            if segmento.is_some() {
+                return Err("Segmento só se aplica a boletos de arrecadação".into());
+            }
+
+            let banco = match banco {
+                Some(banco) if banco.len() == 3 && banco.chars().all(|c| c.is_ascii_digit()) => {
+                    digitos(banco)
+                }
+                Some(banco) => return Err(format!("Código de banco inválido: {}", banco)),
+                None => digitos(BANCOS.choose(rng).unwrap().0),
+            };
+            let fator = match vencimento {
+                Some(vencimento) => {
+                    let data = NaiveDate::parse_from_str(vencimento, "%Y-%m-%d").map_err(|_| {
+                        format!("Vencimento inválido: {} (use AAAA-MM-DD)", vencimento)
+                    })?;
+                    fator_vencimento(data)?
+                }
+                None => rng.random_range(1000..=9999),
+            };
+            let valor = match valor_centavos {
+                Some(valor) if valor >= 10_000_000_000 => {
+                    return Err("Valor deve ter no máximo 10 dígitos".into())
+                }
+                Some(valor) => valor,
+                None => rng.random_range(100..=1_000_000),
+            };
+
+            let mut barras = banco;
+            barras.push(MOEDA_REAL);
+            barras.extend(digitos(&format!("{:04}{:010}", fator, valor)));
+            barras.extend(sortear_digitos(rng, 25));
+            let dv = dv_bancario(&barras);
+            barras.insert(4, dv);
+            barras
+        }
+        TipoBoleto::Arrecadacao => {
+            if banco.is_some() || vencimento.is_some() {
+                return Err("Boletos de arrecadação não têm banco nem fator de vencimento".into());
+            }
+
+            let segmento = match segmento {
+                Some(segmento) if nome_segmento(segmento).is_some() => segmento,
+                Some(segmento) => return Err(format!("Segmento inválido: {}", segmento)),
+                None => rng.random_range(1..=7),
+            };
+            let valor = match valor_centavos {
+                Some(valor) if valor >= 100_000_000_000 => {
+                    return Err("Valor deve ter no máximo 11 dígitos".into())
+                }
+                Some(valor) => valor,
+                None => rng.random_range(100..=1_000_000),
+            };
+            let identificador = *IDENTIFICADORES_VALOR_EFETIVO.choose(rng).unwrap();
+            let modulo = modulo_arrecadacao(identificador).unwrap();
+
+            let mut barras = vec![PRODUTO_ARRECADACAO, segmento, identificador];
+            barras.extend(digitos(&format!("{:011}", valor)));
+            barras.extend(sortear_digitos(rng, 29));
+            let dv = modulo(&barras);
+            barras.insert(3, dv);
+            barras
+        }
+    };
+
+    Ok(decodificar(tipo, &barras))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINHA_BANCARIA: &str = "00190.50095 40144.816069 06809.350314 3 37370000000100";
+    const BARRAS_BANCARIO: &str = "00193373700000001000500940144816060680935031";
+    const LINHA_ARRECADACAO: &str = "83620000000-5 66780048100-0 18097565731-3 00158963608-1";
+    const BARRAS_ARRECADACAO: &str = "83620000000667800481001809756573100158963608";
+
+    /// Troca o dígito na posição `posicao` (entre os dígitos) por outro.
+    fn trocar_digito(valor: &str, posicao: usize) -> String {
+        let mut digits = digitos(valor);
+        digits[posicao] = (digits[posicao] + 1) % 10;
+        para_string(&digits)
+    }
+
+    #[test]
+    fn aceita_e_decodifica_boletos_conhecidos() {
+        for entrada in [LINHA_BANCARIA, BARRAS_BANCARIO] {
+            let validacao = validar_boleto(entrada);
+            assert!(validacao.relatorio.valid, "{}", entrada);
+            let boleto = validacao.boleto.unwrap();
+            assert_eq!(boleto.tipo, TipoBoleto::Bancario);
+            assert_eq!(boleto.codigo_barras, BARRAS_BANCARIO);
+            assert_eq!(boleto.linha_digitavel, LINHA_BANCARIA);
+            assert_eq!(boleto.banco.as_deref(), Some("001"));
+            assert_eq!(boleto.nome_banco.as_deref(), Some("Banco do Brasil"));
+            assert_eq!(boleto.fator_vencimento, Some(3737));
+            assert_eq!(boleto.valor_centavos, Some(100));
+        }
+
+        for entrada in [LINHA_ARRECADACAO, BARRAS_ARRECADACAO] {
+            let validacao = validar_boleto(entrada);
+            assert!(validacao.relatorio.valid, "{}", entrada);
+            let boleto = validacao.boleto.unwrap();
+            assert_eq!(boleto.tipo, TipoBoleto::Arrecadacao);
+            assert_eq!(boleto.linha_digitavel, LINHA_ARRECADACAO);
+            assert_eq!(boleto.segmento, Some(3));
+            assert_eq!(boleto.valor_centavos, Some(6678));
+        }
+    }
+
+    #[test]
+    fn reprova_cada_digito_verificador_errado() {
+        for (posicao, campo) in [(9, 1), (20, 2), (31, 3)] {
+            let validacao = validar_boleto(&trocar_digito(LINHA_BANCARIA, posicao));
+            assert_eq!(
+                validacao.relatorio.failure,
+                Some(ValidationFailure::FieldCheckDigitMismatch)
+            );
+            assert_eq!(validacao.campo, Some(campo));
+        }
+        for entrada in [
+            trocar_digito(LINHA_BANCARIA, 32),
+            trocar_digito(BARRAS_BANCARIO, 4),
+            trocar_digito(BARRAS_ARRECADACAO, 3),
+        ] {
+            assert_eq!(
+                validar_boleto(&entrada).relatorio.failure,
+                Some(ValidationFailure::ChecksumMismatch),
+                "{}",
+                entrada
+            );
+        }
+        for campo in 1..=4 {
+            let validacao = validar_boleto(&trocar_digito(LINHA_ARRECADACAO, campo * 12 - 1));
+            assert_eq!(validacao.campo, Some(campo));
+        }
+    }
+
+    #[test]
+    fn reprova_tamanho_moeda_e_caracteres() {
+        let falha = |entrada: &str| validar_boleto(entrada).relatorio.failure;
+        assert_eq!(falha("0019050095"), Some(ValidationFailure::WrongLength));
+        assert_eq!(
+            falha(&format!("0018{}", &BARRAS_BANCARIO[4..])),
+            Some(ValidationFailure::InvalidPrefix)
+        );
+        assert_eq!(
+            falha("00190.50095 40144.816069 06809.350314 3 3737000000010A"),
+            Some(ValidationFailure::InvalidCharacters)
+        );
+    }
+
+    #[test]
+    fn converte_linha_digitavel_e_codigo_de_barras() {
+        assert_eq!(converter_boleto(LINHA_BANCARIA).unwrap(), BARRAS_BANCARIO);
+        assert_eq!(converter_boleto(BARRAS_BANCARIO).unwrap(), LINHA_BANCARIA);
+        assert_eq!(
+            converter_boleto(LINHA_ARRECADACAO).unwrap(),
+            BARRAS_ARRECADACAO
+        );
+        assert!(converter_boleto(&trocar_digito(LINHA_BANCARIA, 9)).is_err());
+    }
+
+    #[test]
+    fn fator_de_vencimento_reinicia_em_1000() {
+        let data = |texto| NaiveDate::parse_from_str(texto, "%Y-%m-%d").unwrap();
+        assert_eq!(fator_vencimento(data("2000-07-03")), Ok(1000));
+        assert_eq!(fator_vencimento(data("2025-02-21")), Ok(9999));
+        assert_eq!(fator_vencimento(data("2025-02-22")), Ok(1000));
+        assert!(fator_vencimento(data("2000-07-02")).is_err());
+        assert_eq!(data_do_fator(1000, data("2025-03-01")), data("2025-02-22"));
+        assert_eq!(data_do_fator(1000, data("2001-01-01")), data("2000-07-03"));
+    }
+
+    #[test]
+    fn boletos_gerados_passam_na_validacao() {
+        for semente in 0..100 {
+            for tipo in [TipoBoleto::Bancario, TipoBoleto::Arrecadacao] {
+                let boleto = gerar_boleto(tipo, None, None, None, None, Some(semente)).unwrap();
+                for entrada in [&boleto.linha_digitavel, &boleto.codigo_barras] {
+                    let validacao = validar_boleto(entrada);
+                    assert!(validacao.relatorio.valid, "{}", entrada);
+                    assert_eq!(validacao.boleto.unwrap().tipo, tipo);
+                }
+            }
+        }
+
+        let boleto = gerar_boleto(
+            TipoBoleto::Bancario,
+            Some("341"),
+            Some("2026-12-01"),
+            Some(12_345),
+            None,
+            Some(1),
+        )
+        .unwrap();
+        assert_eq!(boleto.banco.as_deref(), Some("341"));
+        assert_eq!(boleto.vencimento.as_deref(), Some("2026-12-01"));
+        assert_eq!(boleto.valor_centavos, Some(12_345));
+        assert!(gerar_boleto(TipoBoleto::Bancario, None, None, None, Some(1), None).is_err());
+        assert!(
+            gerar_boleto(TipoBoleto::Arrecadacao, Some("001"), None, None, None, None).is_err()
+        );
+    }
+}
@@ -0,0 +1,488 @@
+use crate::cpf_cnpj::{validar_cnpj, validar_cpf, GeradorDocumentos};
+use chrono::Local;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Códigos IBGE das UFs, usados nos dois primeiros dígitos da chave.
+const UFS_IBGE: [(&str, &str); 27] = [
+    ("11", "RO"),
+    ("12", "AC"),
+    ("13", "AM"),
+    ("14", "RR"),
+    ("15", "PA"),
+    ("16", "AP"),
+    ("17", "TO"),
+    ("21", "MA"),
+    ("22", "PI"),
+    ("23", "CE"),
+    ("24", "RN"),
+    ("25", "PB"),
+    ("26", "PE"),
+    ("27", "AL"),
+    ("28", "SE"),
+    ("29", "BA"),
+    ("31", "MG"),
+    ("32", "ES"),
+    ("33", "RJ"),
+    ("35", "SP"),
+    ("41", "PR"),
+    ("42", "SC"),
+    ("43", "RS"),
+    ("50", "MS"),
+    ("51", "MT"),
+    ("52", "GO"),
+    ("53", "DF"),
+];
+
+const MODELO_NFE: u32 = 55;
+const MAX_SERIE: u32 = 999;
+const MAX_NUMERO: u32 = 999_999_999;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcoesChaveAcesso {
+    /// Sigla da UF do emitente.
+    pub uf: Option<String>,
+    /// Ano e mês de emissão no formato `AAMM`; sem ele, usa o mês atual.
+    pub ano_mes: Option<String>,
+    pub cnpj: Option<String>,
+    pub modelo: Option<u32>,
+    pub serie: Option<u32>,
+    pub numero: Option<u32>,
+    pub tipo_emissao: Option<u32>,
+    #[serde(default)]
+    pub alfanumerico: bool,
+    pub semente: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CamposChaveAcesso {
+    pub codigo_uf: String,
+    pub uf: Option<String>,
+    pub ano_mes: String,
+    /// CNPJ do emitente, ou `000` + CPF quando o emitente é pessoa física.
+    pub cnpj: String,
+    pub modelo: String,
+    pub nome_modelo: Option<String>,
+    pub serie: String,
+    pub numero: String,
+    pub tipo_emissao: String,
+    pub nome_tipo_emissao: Option<String>,
+    pub codigo_numerico: String,
+    pub digito_verificador: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ErroChaveAcesso {
+    /// Nome do campo em `CamposChaveAcesso` (ou `chave` para erros de formato).
+    pub campo: String,
+    pub mensagem: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ChaveAcesso {
+    pub valida: bool,
+    pub chave: String,
+    /// Chave em grupos de 4, como impressa no DANFE.
+    pub chave_formatada: String,
+    pub campos: Option<CamposChaveAcesso>,
+    pub erros: Vec<ErroChaveAcesso>,
+}
+
+fn nome_modelo(modelo: &str) -> Option<&'static str> {
+    match modelo {
+        "55" => Some("NF-e"),
+        "57" => Some("CT-e"),
+        "58" => Some("MDF-e"),
+        "62" => Some("NFCom"),
+        "65" => Some("NFC-e"),
+        "66" => Some("NF3e"),
+        "67" => Some("CT-e OS"),
+        _ => None,
+    }
+}
+
+fn nome_tipo_emissao(tipo: &str) -> Option<&'static str> {
+    match tipo {
+        "1" => Some("Normal"),
+        "2" => Some("Contingência FS-IA"),
+        "3" => Some("Contingência SCAN"),
+        "4" => Some("Contingência EPEC"),
+        "5" => Some("Contingência FS-DA"),
+        "6" => Some("Contingência SVC-AN"),
+        "7" => Some("Contingência SVC-RS"),
+        "8" => Some("Contingência SVC-SP"),
+        "9" => Some("Contingência off-line da NFC-e"),
+        _ => None,
+    }
+}
+
+fn uf_do_codigo(codigo: &str) -> Option<&'static str> {
+    UFS_IBGE
+        .iter()
+        .find(|(c, _)| *c == codigo)
+        .map(|(_, uf)| *uf)
+}
+
+fn codigo_da_uf(uf: &str) -> Result<&'static str, String> {
+    UFS_IBGE
+        .iter()
+        .find(|(_, sigla)| sigla.eq_ignore_ascii_case(uf))
+        .map(|(codigo, _)| *codigo)
+        .ok_or_else(|| format!("UF inválida: {}", uf))
+}
+
+/// Módulo 11 com pesos de 2 a 9 da direita para a esquerda; restos 0 e 1 viram 0.
+/// Letras do CNPJ alfanumérico valem o código ASCII menos 48, como no próprio CNPJ.
+fn digito_verificador(chave: &str) -> u32 {
+    let soma: u32 = chave
+        .chars()
+        .rev()
+        .zip((2..=9).cycle())
+        .map(|(c, peso)| (c as u32 - '0' as u32) * peso)
+        .sum();
+    match soma % 11 {
+        0 | 1 => 0,
+        resto => 11 - resto,
+    }
+}
+
+fn formatar_chave(chave: &str) -> String {
+    chave
+        .chars()
+        .collect::<Vec<_>>()
+        .chunks(4)
+        .map(|grupo| grupo.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn so_digitos(valor: &str) -> bool {
+    valor.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Confere só o formato e o mês: uma chave recebida é decodificada mesmo que o relógio
+/// local esteja atrasado em relação ao emitente.
+fn validar_ano_mes(ano_mes: &str) -> Result<(), String> {
+    if ano_mes.len() != 4 || !so_digitos(ano_mes) {
+        return Err("Ano e mês devem estar no formato AAMM".into());
+    }
+    let mes: u32 = ano_mes[2..].parse().unwrap();
+    if !(1..=12).contains(&mes) {
+        return Err(format!("Mês inválido: {:02}", mes));
+    }
+    Ok(())
+}
+
+/// Aceita CNPJ (numérico ou alfanumérico) ou CPF completado com `000` à esquerda.
+fn emitente_valido(documento: &str) -> bool {
+    validar_cnpj(documento).valid
+        || (documento.starts_with("000") && validar_cpf(&documento[3..]).valid)
+}
+
+/// Gera uma chave de acesso válida. Campos omitidos são sorteados, exceto o modelo (55),
+/// o tipo de emissão (1, normal) e o ano/mês (o atual).
+pub fn gerar_chave_acesso(opcoes: &OpcoesChaveAcesso) -> Result<ChaveAcesso, String> {
+    let mut gerador = GeradorDocumentos::new(opcoes.semente);
+
+    let cnpj = match opcoes.cnpj.as_deref() {
+        Some(cnpj) => {
+            let relatorio = validar_cnpj(cnpj);
+            if !relatorio.valid {
+                return Err(format!("CNPJ inválido: {}", cnpj));
+            }
+            relatorio.normalized
+        }
+        None => gerador.cnpj(false, opcoes.alfanumerico),
+    };
+
+    let rng = gerador.rng();
+    let codigo_uf = match opcoes.uf.as_deref() {
+        Some(uf) => codigo_da_uf(uf)?,
+        None => UFS_IBGE.choose(rng).unwrap().0,
+    };
+    let ano_mes = match opcoes.ano_mes.as_deref() {
+        Some(ano_mes) => {
+            validar_ano_mes(ano_mes)?;
+            // AAMM com dígitos fixos: a comparação de texto segue a ordem cronológica.
+            if ano_mes > Local::now().format("%y%m").to_string().as_str() {
+                return Err("Ano e mês de emissão no futuro".into());
+            }
+            ano_mes.to_string()
+        }
+        None => Local::now().format("%y%m").to_string(),
+    };
+    let modelo = opcoes.modelo.unwrap_or(MODELO_NFE);
+    if nome_modelo(&modelo.to_string()).is_none() {
+        return Err(format!("Modelo inválido: {}", modelo));
+    }
+    let serie = opcoes
+        .serie
+        .unwrap_or_else(|| rng.random_range(1..=MAX_SERIE));
+    if serie > MAX_SERIE {
+        return Err(format!("Série deve ser no máximo {}", MAX_SERIE));
+    }
+    let numero = opcoes
+        .numero
+        .unwrap_or_else(|| rng.random_range(1..=MAX_NUMERO));
+    if !(1..=MAX_NUMERO).contains(&numero) {
+        return Err(format!("Número deve estar entre 1 e {}", MAX_NUMERO));
+    }
+    let tipo_emissao = opcoes.tipo_emissao.unwrap_or(1);
+    if nome_tipo_emissao(&tipo_emissao.to_string()).is_none() {
+        return Err(format!("Tipo de emissão inválido: {}", tipo_emissao));
+    }
+    // A SEFAZ rejeita código numérico igual ao número do documento.
+    let codigo_numerico = loop {
+        let codigo = rng.random_range(0..100_000_000u32);
+        if codigo != numero % 100_000_000 {
+            break codigo;
+        }
+    };
+
+    let mut chave = format!(
+        "{}{}{}{:02}{:03}{:09}{}{:08}",
+        codigo_uf, ano_mes, cnpj, modelo, serie, numero, tipo_emissao, codigo_numerico
+    );
+    chave.push_str(&digito_verificador(&chave).to_string());
+
+    Ok(decodificar_chave_acesso(&chave))
+}
+
+/// Separa a chave nos seus campos e aponta cada parte inconsistente.
+pub fn decodificar_chave_acesso(chave_input: &str) -> ChaveAcesso {
+    let chave: String = chave_input
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut erros = Vec::new();
+    let mut erro = |campo: &str, mensagem: String| {
+        erros.push(ErroChaveAcesso {
+            campo: campo.to_string(),
+            mensagem,
+        })
+    };
+
+    if chave.len() != 44 {
+        erro(
+            "chave",
+            format!(
+                "A chave deve ter 44 caracteres (informados {})",
+                chave.len()
+            ),
+        );
+        return ChaveAcesso {
+            valida: false,
+            chave_formatada: formatar_chave(&chave),
+            chave,
+            campos: None,
+            erros,
+        };
+    }
+
+    let campos = CamposChaveAcesso {
+        codigo_uf: chave[0..2].to_string(),
+        uf: uf_do_codigo(&chave[0..2]).map(String::from),
+        ano_mes: chave[2..6].to_string(),
+        cnpj: chave[6..20].to_string(),
+        modelo: chave[20..22].to_string(),
+        nome_modelo: nome_modelo(&chave[20..22]).map(String::from),
+        serie: chave[22..25].to_string(),
+        numero: chave[25..34].to_string(),
+        tipo_emissao: chave[34..35].to_string(),
+        nome_tipo_emissao: nome_tipo_emissao(&chave[34..35]).map(String::from),
+        codigo_numerico: chave[35..43].to_string(),
+        digito_verificador: chave[43..44].to_string(),
+    };
+
+    if !so_digitos(&chave[..6]) || !so_digitos(&chave[20..]) {
+        erro(
+            "chave",
+            "Só o CNPJ do emitente pode conter letras".to_string(),
+        );
+    }
+    if campos.uf.is_none() {
+        erro(
+            "codigoUf",
+            format!("Código de UF inexistente: {}", campos.codigo_uf),
+        );
+    }
+    if let Err(mensagem) = validar_ano_mes(&campos.ano_mes) {
+        erro("anoMes", mensagem);
+    }
+    if !emitente_valido(&campos.cnpj) {
+        erro(
+            "cnpj",
+            format!("CNPJ/CPF do emitente inválido: {}", campos.cnpj),
+        );
+    }
+    if campos.nome_modelo.is_none() {
+        erro("modelo", format!("Modelo desconhecido: {}", campos.modelo));
+    }
+    if campos.numero.chars().all(|c| c == '0') {
+        erro(
+            "numero",
+            "Número do documento não pode ser zero".to_string(),
+        );
+    }
+    if campos.nome_tipo_emissao.is_none() {
+        erro(
+            "tipoEmissao",
+            format!("Tipo de emissão desconhecido: {}", campos.tipo_emissao),
+        );
+    }
+    if campos.codigo_numerico == campos.numero[1..] {
+        erro(
+            "codigoNumerico",
+            "Código numérico não pode ser igual ao número do documento".to_string(),
+        );
+    }
+    if so_digitos(&chave[..6]) && so_digitos(&chave[20..]) {
+        let esperado = digito_verificador(&chave[..43]).to_string();
+        if esperado != campos.digito_verificador {
+            erro(
+                "digitoVerificador",
+                format!(
+                    "Dígito verificador esperado {}, informado {}",
+                    esperado, campos.digito_verificador
+                ),
+            );
+        }
+    }
+
+    ChaveAcesso {
+        valida: erros.is_empty(),
+        chave_formatada: formatar_chave(&chave),
+        chave,
+        campos: Some(campos),
+        erros,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exemplo do Manual de Orientação do Contribuinte da NF-e.
+    const EXEMPLO_MANUAL: &str = "35080599999090910270550010000000015180051273";
+
+    fn campos_com_erro(chave: &str) -> Vec<String> {
+        decodificar_chave_acesso(chave)
+            .erros
+            .into_iter()
+            .map(|erro| erro.campo)
+            .collect()
+    }
+
+    fn com_digito(sem_dv: &str) -> String {
+        format!("{}{}", sem_dv, digito_verificador(sem_dv))
+    }
+
+    #[test]
+    fn decodifica_o_exemplo_do_manual() {
+        let chave = decodificar_chave_acesso(&formatar_chave(EXEMPLO_MANUAL));
+        assert!(chave.valida, "{:?}", chave.erros);
+        assert_eq!(chave.chave, EXEMPLO_MANUAL);
+        let campos = chave.campos.unwrap();
+        assert_eq!(campos.uf.as_deref(), Some("SP"));
+        assert_eq!(campos.ano_mes, "0805");
+        assert_eq!(campos.cnpj, "99999090910270");
+        assert_eq!(campos.nome_modelo.as_deref(), Some("NF-e"));
+        assert_eq!(campos.serie, "001");
+        assert_eq!(campos.numero, "000000001");
+        assert_eq!(campos.tipo_emissao, "5");
+        assert_eq!(campos.codigo_numerico, "18005127");
+        assert_eq!(campos.digito_verificador, "3");
+    }
+
+    #[test]
+    fn aponta_o_campo_inconsistente() {
+        let dv_errado = format!("{}4", &EXEMPLO_MANUAL[..43]);
+        assert_eq!(campos_com_erro(&dv_errado), ["digitoVerificador"]);
+        assert_eq!(campos_com_erro(&EXEMPLO_MANUAL[..40]), ["chave"]);
+
+        let uf_inexistente = com_digito(&format!("10{}", &EXEMPLO_MANUAL[2..43]));
+        assert_eq!(campos_com_erro(&uf_inexistente), ["codigoUf"]);
+        let mes_13 = com_digito(&format!("350813{}", &EXEMPLO_MANUAL[6..43]));
+        assert_eq!(campos_com_erro(&mes_13), ["anoMes"]);
+        let cnpj_invalido = com_digito(&format!("35080599999090910271{}", &EXEMPLO_MANUAL[20..43]));
+        assert_eq!(campos_com_erro(&cnpj_invalido), ["cnpj"]);
+    }
+
+    #[test]
+    fn decodifica_chave_de_mes_futuro() {
+        let futura = com_digito(&format!("359912{}", &EXEMPLO_MANUAL[6..43]));
+        assert!(decodificar_chave_acesso(&futura).valida);
+
+        let opcoes = OpcoesChaveAcesso {
+            ano_mes: Some("9912".to_string()),
+            ..Default::default()
+        };
+        assert!(gerar_chave_acesso(&opcoes).is_err());
+    }
+
+    #[test]
+    fn aceita_cpf_do_emitente_com_zeros() {
+        let chave = com_digito(&format!(
+            "350805000{}{}",
+            "52998224725",
+            &EXEMPLO_MANUAL[20..43]
+        ));
+        assert!(decodificar_chave_acesso(&chave).valida);
+    }
+
+    #[test]
+    fn chaves_geradas_passam_na_decodificacao() {
+        for semente in 0..100 {
+            for alfanumerico in [false, true] {
+                let opcoes = OpcoesChaveAcesso {
+                    alfanumerico,
+                    semente: Some(semente),
+                    ..Default::default()
+                };
+                let chave = gerar_chave_acesso(&opcoes).unwrap();
+                assert!(chave.valida, "{}: {:?}", chave.chave, chave.erros);
+            }
+        }
+
+        let opcoes = OpcoesChaveAcesso {
+            uf: Some("sp".to_string()),
+            ano_mes: Some("2401".to_string()),
+            cnpj: Some("11.222.333/0001-81".to_string()),
+            modelo: Some(65),
+            serie: Some(2),
+            numero: Some(123),
+            tipo_emissao: Some(9),
+            ..Default::default()
+        };
+        let chave = gerar_chave_acesso(&opcoes).unwrap();
+        assert!(chave
+            .chave
+            .starts_with("35240111222333000181650020000001239"));
+        for invalida in [
+            OpcoesChaveAcesso {
+                uf: Some("XX".into()),
+                ..opcoes.clone()
+            },
+            OpcoesChaveAcesso {
+                modelo: Some(56),
+                ..opcoes.clone()
+            },
+            OpcoesChaveAcesso {
+                numero: Some(0),
+                ..opcoes.clone()
+            },
+            OpcoesChaveAcesso {
+                cnpj: Some("11222333000182".into()),
+                ..opcoes.clone()
+            },
+        ] {
+            assert!(gerar_chave_acesso(&invalida).is_err());
+        }
+    }
+}
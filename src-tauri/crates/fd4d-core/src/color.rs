@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PixelColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub hex: String,
+    pub rgb: String,
+    pub cmyk: String,
+}
+
+impl PixelColor {
+    /// Monta a cor com as representações em HEX, RGB e CMYK já formatadas.
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        let (c, m, y, k) = rgb_to_cmyk(r, g, b);
+
+        PixelColor {
+            r,
+            g,
+            b,
+            hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
+            rgb: format!("rgb({}, {}, {})", r, g, b),
+            cmyk: format!("cmyk({:.0}%, {:.0}%, {:.0}%, {:.0}%)", c, m, y, k),
+        }
+    }
+
+    /// Aceita `#RRGGBB` ou `RRGGBB`.
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let hex_clean = hex.trim().trim_start_matches('#');
+        if hex_clean.len() != 6 || !hex_clean.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Formato hex inválido: {}", hex));
+        }
+
+        let canal = |i: usize| u8::from_str_radix(&hex_clean[i..i + 2], 16).unwrap();
+        Ok(Self::from_rgb(canal(0), canal(2), canal(4)))
+    }
+}
+
+/// Converte RGB para CMYK, com cada componente em porcentagem (0 a 100).
+pub fn rgb_to_cmyk(r: u8, g: u8, b: u8) -> (f64, f64, f64, f64) {
+    if r == 0 && g == 0 && b == 0 {
+        return (0.0, 0.0, 0.0, 100.0);
+    }
+
+    let r_prime = r as f64 / 255.0;
+    let g_prime = g as f64 / 255.0;
+    let b_prime = b as f64 / 255.0;
+
+    let k = 1.0 - r_prime.max(g_prime).max(b_prime);
+    let c = (1.0 - r_prime - k) / (1.0 - k);
+    let m = (1.0 - g_prime - k) / (1.0 - k);
+    let y = (1.0 - b_prime - k) / (1.0 - k);
+
+    (c * 100.0, m * 100.0, y * 100.0, k * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formata_hex_rgb_e_cmyk() {
+        let cor = PixelColor::from_rgb(255, 128, 0);
+        assert_eq!(cor.hex, "#FF8000");
+        assert_eq!(cor.rgb, "rgb(255, 128, 0)");
+        assert_eq!(cor.cmyk, "cmyk(0%, 50%, 100%, 0%)");
+        assert_eq!(PixelColor::from_rgb(0, 0, 0).cmyk, "cmyk(0%, 0%, 0%, 100%)");
+        assert_eq!(
+            PixelColor::from_rgb(255, 255, 255).cmyk,
+            "cmyk(0%, 0%, 0%, 0%)"
+        );
+    }
+
+    #[test]
+    fn le_hex_com_ou_sem_cerquilha() {
+        let cor = PixelColor::from_hex(" #1a2B3c ").unwrap();
+        assert_eq!((cor.r, cor.g, cor.b), (0x1A, 0x2B, 0x3C));
+        assert_eq!(PixelColor::from_hex("1A2B3C").unwrap().hex, "#1A2B3C");
+        assert!(PixelColor::from_hex("#12345").is_err());
+        assert!(PixelColor::from_hex("#12345G").is_err());
+    }
+}
@@ -0,0 +1,1000 @@
+use crate::validacao::{mascara_valida, ValidationFailure, ValidationReport};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+fn calculate_digit(cpf: &[u32], inicial_weight: u32) -> u32 {
+    let sum: u32 = cpf
+        .iter()
+        .enumerate()
+        .map(|(i, &digit)| digit * (inicial_weight - i as u32))
+        .sum();
+
+    let rest: u32 = sum % 11;
+    if rest < 2 {
+        0
+    } else {
+        11 - rest
+    }
+}
+
+const MASCARA_CPF: &str = "###.###.###-##";
+const MASCARA_CNPJ: &str = "##.###.###/####-##";
+
+/// Preenche os `#` da máscara com os caracteres de `valor`. Com entrada parcial, para no
+/// último caractere informado (`"1234"` vira `"123.4"`), o que serve para formatar ao digitar.
+pub(crate) fn aplicar_mascara(mascara: &str, valor: &str) -> String {
+    let mut caracteres = valor.chars().peekable();
+    let mut resultado = String::with_capacity(mascara.len());
+    for c in mascara.chars() {
+        if caracteres.peek().is_none() {
+            break;
+        }
+        resultado.push(if c == '#' {
+            caracteres.next().unwrap()
+        } else {
+            c
+        });
+    }
+    resultado
+}
+
+fn formatar_cpf(cpf: &str) -> String {
+    aplicar_mascara(MASCARA_CPF, cpf)
+}
+
+/// UFs de cada região fiscal, indexadas pelo 9º dígito do CPF.
+const REGIOES_FISCAIS: [&[&str]; 10] = [
+    &["RS"],
+    &["DF", "GO", "MS", "MT", "TO"],
+    &["AC", "AM", "AP", "PA", "RO", "RR"],
+    &["CE", "MA", "PI"],
+    &["AL", "PB", "PE", "RN"],
+    &["BA", "SE"],
+    &["MG"],
+    &["ES", "RJ"],
+    &["SP"],
+    &["PR", "SC"],
+];
+
+/// Converte uma UF (ex.: "SP") no dígito da sua região fiscal.
+pub fn regiao_fiscal_da_uf(uf: &str) -> Result<u32, String> {
+    let uf = uf.trim().to_ascii_uppercase();
+    REGIOES_FISCAIS
+        .iter()
+        .position(|ufs| ufs.contains(&uf.as_str()))
+        .map(|regiao| regiao as u32)
+        .ok_or_else(|| format!("UF desconhecida: {}", uf))
+}
+
+fn ufs_da_regiao_fiscal(regiao: u32) -> Vec<String> {
+    REGIOES_FISCAIS[regiao as usize]
+        .iter()
+        .map(|uf| uf.to_string())
+        .collect()
+}
+
+fn montar_cpf<R: Rng + ?Sized>(rng: &mut R, com_mascara: bool, regiao: Option<u32>) -> String {
+    let mut cpf: Vec<u32> = (0..8).map(|_| rng.random_range(0..10)).collect();
+    cpf.push(regiao.unwrap_or_else(|| rng.random_range(0..10)));
+
+    let first_digit = calculate_digit(&cpf, 10);
+    cpf.push(first_digit);
+
+    let second_digit = calculate_digit(&cpf, 11);
+    cpf.push(second_digit);
+
+    let cpf: String = cpf.iter().map(|d| d.to_string()).collect();
+
+    if com_mascara {
+        formatar_cpf(&cpf)
+    } else {
+        cpf
+    }
+}
+
+/// Gera um CPF válido. Com `uf`, o 9º dígito corresponde à região fiscal da UF.
+pub fn gerar_cpf(
+    com_mascara: bool,
+    uf: Option<&str>,
+    semente: Option<u64>,
+) -> Result<String, String> {
+    let regiao = uf.map(regiao_fiscal_da_uf).transpose()?;
+    Ok(GeradorDocumentos::new(semente).cpf(com_mascara, regiao))
+}
+
+const CNPJ_FIRST_WEIGHTS: [u32; 12] = [5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+const CNPJ_SECOND_WEIGHTS: [u32; 13] = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+const CNPJ_ALFANUMERICO: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Valor de um caractere do CNPJ no cálculo do DV: código ASCII menos 48.
+/// Para dígitos equivale ao próprio dígito; letras maiúsculas valem de 17 (A) a 42 (Z).
+fn cnpj_char_value(c: char) -> Option<u32> {
+    if c.is_ascii_digit() || c.is_ascii_uppercase() {
+        Some(c as u32 - 48)
+    } else {
+        None
+    }
+}
+
+fn calculate_cnpj_digit(values: &[u32], weights: &[u32]) -> u32 {
+    let sum: u32 = values
+        .iter()
+        .zip(weights.iter())
+        .map(|(&value, &weight)| value * weight)
+        .sum();
+
+    let rest = sum % 11;
+    if rest < 2 {
+        0
+    } else {
+        11 - rest
+    }
+}
+
+fn formatar_cnpj(cnpj: &str) -> String {
+    aplicar_mascara(MASCARA_CNPJ, cnpj)
+}
+
+/// Acrescenta os dois dígitos verificadores à base de 12 caracteres do CNPJ.
+fn completar_cnpj(base: &str) -> String {
+    let mut values: Vec<u32> = base.chars().filter_map(cnpj_char_value).collect();
+
+    let first_digit = calculate_cnpj_digit(&values, &CNPJ_FIRST_WEIGHTS);
+    values.push(first_digit);
+
+    let second_digit = calculate_cnpj_digit(&values, &CNPJ_SECOND_WEIGHTS);
+
+    format!("{}{}{}", base, first_digit, second_digit)
+}
+
+const MAX_FILIAL: u32 = 9999;
+
+fn sortear_alfanumericos<R: Rng + ?Sized>(rng: &mut R, quantidade: usize) -> String {
+    (0..quantidade)
+        .map(|_| CNPJ_ALFANUMERICO[rng.random_range(0..CNPJ_ALFANUMERICO.len())] as char)
+        .collect()
+}
+
+fn sortear_raiz_cnpj<R: Rng + ?Sized>(rng: &mut R, alfanumerico: bool) -> String {
+    if alfanumerico {
+        sortear_alfanumericos(rng, 8)
+    } else {
+        (0..8)
+            .map(|_| char::from(b'0' + rng.random_range(0..10u8)))
+            .collect()
+    }
+}
+
+/// Número de ordem alfanumérico (posições 9 a 12). Nunca `0000`, que não identifica
+/// estabelecimento; com `excluir_matriz`, também nunca `0001`.
+fn sortear_ordem_alfanumerica<R: Rng + ?Sized>(rng: &mut R, excluir_matriz: bool) -> String {
+    loop {
+        let ordem = sortear_alfanumericos(rng, 4);
+        if ordem != "0000" && !(excluir_matriz && ordem == "0001") {
+            return ordem;
+        }
+    }
+}
+
+fn montar_cnpj(raiz: &str, ordem: &str, com_mascara: bool) -> String {
+    let cnpj = completar_cnpj(&format!("{}{}", raiz, ordem));
+
+    if com_mascara {
+        formatar_cnpj(&cnpj)
+    } else {
+        cnpj
+    }
+}
+
+fn montar_cnpj_da_raiz(raiz: &str, filial: u32, com_mascara: bool) -> String {
+    montar_cnpj(raiz, &format!("{:04}", filial), com_mascara)
+}
+
+/// Gerador de CPFs/CNPJs com RNG próprio. Com `semente`, a mesma semente produz sempre a
+/// mesma sequência de documentos, em qualquer máquina (ChaCha8 é portável entre plataformas).
+pub struct GeradorDocumentos {
+    rng: ChaCha8Rng,
+}
+
+impl GeradorDocumentos {
+    pub fn new(semente: Option<u64>) -> Self {
+        let rng = match semente {
+            Some(semente) => ChaCha8Rng::seed_from_u64(semente),
+            None => ChaCha8Rng::from_rng(&mut rand::rng()),
+        };
+        GeradorDocumentos { rng }
+    }
+
+    pub fn cpf(&mut self, com_mascara: bool, regiao: Option<u32>) -> String {
+        montar_cpf(&mut self.rng, com_mascara, regiao)
+    }
+
+    pub fn raiz_cnpj(&mut self, alfanumerico: bool) -> String {
+        sortear_raiz_cnpj(&mut self.rng, alfanumerico)
+    }
+
+    pub(crate) fn rng(&mut self) -> &mut ChaCha8Rng {
+        &mut self.rng
+    }
+
+    pub fn filial_aleatoria(&mut self) -> u32 {
+        self.rng.random_range(2..=MAX_FILIAL)
+    }
+
+    /// Semente tirada da sequência deste gerador, para os geradores de outros documentos
+    /// que recebem `semente`. Um lote inteiro continua reproduzível a partir de uma semente só.
+    pub fn semente_derivada(&mut self) -> u64 {
+        self.rng.random()
+    }
+
+    /// CNPJ de matriz (0001) ou, com `alfanumerico`, com os 12 primeiros caracteres sorteados
+    /// do alfabeto alfanumérico, número de ordem incluído.
+    pub fn cnpj(&mut self, com_mascara: bool, alfanumerico: bool) -> String {
+        let raiz = self.raiz_cnpj(alfanumerico);
+        if alfanumerico {
+            let ordem = sortear_ordem_alfanumerica(&mut self.rng, false);
+            montar_cnpj(&raiz, &ordem, com_mascara)
+        } else {
+            montar_cnpj_da_raiz(&raiz, 1, com_mascara)
+        }
+    }
+
+    /// CNPJ de matriz (0001) ou de filial, conforme `matriz`. Com `alfanumerico`, a raiz e o
+    /// número de ordem da filial são sorteados do alfabeto alfanumérico.
+    pub(crate) fn cnpj_matriz_ou_filial(
+        &mut self,
+        com_mascara: bool,
+        alfanumerico: bool,
+        matriz: bool,
+    ) -> String {
+        let raiz = self.raiz_cnpj(alfanumerico);
+        let ordem = if matriz {
+            "0001".to_string()
+        } else if alfanumerico {
+            sortear_ordem_alfanumerica(&mut self.rng, true)
+        } else {
+            format!("{:04}", self.filial_aleatoria())
+        };
+        montar_cnpj(&raiz, &ordem, com_mascara)
+    }
+}
+
+/// Gera um CNPJ com o número de ordem informado em `filial` (0001 = matriz).
+/// Com `filial_aleatoria`, sorteia uma filial entre 0002 e 9999; não pode ser combinado com
+/// `filial`. Com `alfanumerico` e sem `filial`, o número de ordem também é sorteado do
+/// alfabeto alfanumérico.
+pub fn gerar_cnpj(
+    com_mascara: bool,
+    alfanumerico: Option<bool>,
+    filial: Option<u32>,
+    filial_aleatoria: Option<bool>,
+    semente: Option<u64>,
+) -> Result<String, String> {
+    let mut gerador = GeradorDocumentos::new(semente);
+    let alfanumerico = alfanumerico.unwrap_or(false);
+    let filial_aleatoria = filial_aleatoria.unwrap_or(false);
+
+    if filial_aleatoria && filial.is_some() {
+        return Err("Informe a filial ou peça uma filial aleatória, não os dois".to_string());
+    }
+
+    if alfanumerico && filial.is_none() {
+        let raiz = gerador.raiz_cnpj(true);
+        let ordem = sortear_ordem_alfanumerica(&mut gerador.rng, filial_aleatoria);
+        return Ok(montar_cnpj(&raiz, &ordem, com_mascara));
+    }
+
+    let filial = if filial_aleatoria {
+        gerador.filial_aleatoria()
+    } else {
+        filial.unwrap_or(1)
+    };
+
+    if filial == 0 || filial > MAX_FILIAL {
+        return Err(format!(
+            "Número da filial deve estar entre 1 e {}",
+            MAX_FILIAL
+        ));
+    }
+
+    let raiz = gerador.raiz_cnpj(alfanumerico);
+    Ok(montar_cnpj_da_raiz(&raiz, filial, com_mascara))
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FamiliaCnpj {
+    pub raiz: String,
+    pub matriz: String,
+    pub filiais: Vec<String>,
+}
+
+/// Gera uma matriz (0001) e `quantidade_filiais` filiais (0002 em diante) com a mesma raiz.
+pub fn gerar_familia_cnpj(
+    com_mascara: bool,
+    alfanumerico: Option<bool>,
+    quantidade_filiais: u32,
+    semente: Option<u64>,
+) -> Result<FamiliaCnpj, String> {
+    if quantidade_filiais >= MAX_FILIAL {
+        return Err(format!(
+            "Quantidade de filiais deve ser no máximo {}",
+            MAX_FILIAL - 1
+        ));
+    }
+
+    let raiz = GeradorDocumentos::new(semente).raiz_cnpj(alfanumerico.unwrap_or(false));
+
+    Ok(FamiliaCnpj {
+        matriz: montar_cnpj_da_raiz(&raiz, 1, com_mascara),
+        filiais: (2..quantidade_filiais + 2)
+            .map(|filial| montar_cnpj_da_raiz(&raiz, filial, com_mascara))
+            .collect(),
+        raiz,
+    })
+}
+
+fn normalizar_cnpj(cnpj_input: &str) -> String {
+    cnpj_input
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Indica se dois CNPJs pertencem à mesma empresa (mesma raiz de 8 caracteres).
+pub fn mesma_raiz_cnpj(cnpj_a: &str, cnpj_b: &str) -> Result<bool, String> {
+    let a = normalizar_cnpj(cnpj_a);
+    let b = normalizar_cnpj(cnpj_b);
+
+    if a.len() != 14 || b.len() != 14 {
+        return Err("CNPJ deve conter exatamente 14 caracteres".into());
+    }
+
+    Ok(a[..8] == b[..8])
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoDocumento {
+    Cpf,
+    Cnpj,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentoFormatado {
+    pub tipo: TipoDocumento,
+    /// Documento com a máscara aplicada até onde foi digitado.
+    pub valor: String,
+    pub normalizado: String,
+    /// Indica se todos os caracteres do documento já foram informados.
+    pub completo: bool,
+}
+
+impl TipoDocumento {
+    /// Sem tipo informado, letras ou mais de 11 caracteres indicam CNPJ; o resto é CPF.
+    fn detectar(tipo: Option<TipoDocumento>, normalizado: &str) -> TipoDocumento {
+        tipo.unwrap_or_else(|| {
+            if normalizado.len() > 11 || normalizado.chars().any(|c| c.is_ascii_alphabetic()) {
+                TipoDocumento::Cnpj
+            } else {
+                TipoDocumento::Cpf
+            }
+        })
+    }
+
+    fn mascara(self) -> &'static str {
+        match self {
+            TipoDocumento::Cpf => MASCARA_CPF,
+            TipoDocumento::Cnpj => MASCARA_CNPJ,
+        }
+    }
+}
+
+/// Remove a máscara. CPF fica só com dígitos; CNPJ mantém letras, em maiúsculas.
+fn normalizar(tipo: Option<TipoDocumento>, documento: &str) -> (TipoDocumento, String) {
+    let alfanumerico = normalizar_cnpj(documento);
+    let tipo = TipoDocumento::detectar(tipo, &alfanumerico);
+    let normalizado = match tipo {
+        TipoDocumento::Cpf => alfanumerico
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect(),
+        TipoDocumento::Cnpj => alfanumerico,
+    };
+    (tipo, normalizado)
+}
+
+/// Aplica a máscara de CPF ou CNPJ, detectando o tipo se `tipo` não vier. Aceita entrada
+/// parcial para formatar enquanto o usuário digita; o excedente é descartado.
+pub fn formatar_documento(documento: &str, tipo: Option<TipoDocumento>) -> DocumentoFormatado {
+    let (tipo, normalizado) = normalizar(tipo, documento);
+    let tamanho = tipo.mascara().chars().filter(|&c| c == '#').count();
+    let normalizado: String = normalizado.chars().take(tamanho).collect();
+
+    DocumentoFormatado {
+        tipo,
+        valor: aplicar_mascara(tipo.mascara(), &normalizado),
+        completo: normalizado.len() == tamanho,
+        normalizado,
+    }
+}
+
+/// Remove a máscara de um CPF ou CNPJ, detectando o tipo se `tipo` não vier.
+pub fn normalizar_documento(documento: &str, tipo: Option<TipoDocumento>) -> String {
+    normalizar(tipo, documento).1
+}
+
+pub fn validar_cpf(cpf_input: &str) -> ValidationReport {
+    let normalized: String = cpf_input.chars().filter(|c| c.is_ascii_digit()).collect();
+    let mut report = ValidationReport::new(cpf_input, normalized.clone());
+
+    if cpf_input.chars().any(|c| c.is_ascii_alphabetic()) {
+        return report.fail(ValidationFailure::InvalidCharacters);
+    }
+
+    if normalized.len() != 11 {
+        return report.fail(ValidationFailure::WrongLength);
+    }
+
+    let digits: Vec<u32> = normalized.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    let first_digit = calculate_digit(&digits[..9], 10);
+    let mut expected = digits[..9].to_vec();
+    expected.push(first_digit);
+    let second_digit = calculate_digit(&expected, 11);
+
+    report.masked = Some(formatar_cpf(&normalized));
+    report.expected_check_digits = Some(format!("{}{}", first_digit, second_digit));
+    report.supplied_check_digits = Some(normalized[9..].to_string());
+    report.likely_ufs = ufs_da_regiao_fiscal(digits[8]);
+
+    if !mascara_valida(cpf_input) {
+        return report.fail(ValidationFailure::InvalidMask);
+    }
+
+    if digits.windows(2).all(|w| w[0] == w[1]) {
+        return report.fail(ValidationFailure::RepeatedDigits);
+    }
+
+    if first_digit != digits[9] {
+        return report.fail(ValidationFailure::FirstCheckDigitMismatch);
+    }
+
+    if second_digit != digits[10] {
+        return report.fail(ValidationFailure::SecondCheckDigitMismatch);
+    }
+
+    report.pass()
+}
+
+pub fn validar_cnpj(cnpj_input: &str) -> ValidationReport {
+    let normalized = normalizar_cnpj(cnpj_input);
+    let mut report = ValidationReport::new(cnpj_input, normalized.clone());
+
+    if normalized.len() != 14 {
+        return report.fail(ValidationFailure::WrongLength);
+    }
+
+    report.masked = Some(formatar_cnpj(&normalized));
+    report.supplied_check_digits = Some(normalized[12..].to_string());
+
+    if !mascara_valida(cnpj_input) {
+        return report.fail(ValidationFailure::InvalidMask);
+    }
+
+    if !normalized[12..].chars().all(|c| c.is_ascii_digit()) {
+        return report.fail(ValidationFailure::InvalidCharacters);
+    }
+
+    let values: Vec<u32> = normalized.chars().filter_map(cnpj_char_value).collect();
+
+    let first_digit = calculate_cnpj_digit(&values[..12], &CNPJ_FIRST_WEIGHTS);
+    let mut expected = values[..12].to_vec();
+    expected.push(first_digit);
+    let second_digit = calculate_cnpj_digit(&expected, &CNPJ_SECOND_WEIGHTS);
+
+    report.expected_check_digits = Some(format!("{}{}", first_digit, second_digit));
+
+    if values.windows(2).all(|w| w[0] == w[1]) {
+        return report.fail(ValidationFailure::RepeatedDigits);
+    }
+
+    if first_digit != values[12] {
+        return report.fail(ValidationFailure::FirstCheckDigitMismatch);
+    }
+
+    if second_digit != values[13] {
+        return report.fail(ValidationFailure::SecondCheckDigitMismatch);
+    }
+
+    report.pass()
+}
+
+/// Defeito proposital aplicado por `gerar_documentos_invalidos`.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DocumentDefect {
+    WrongFirstCheckDigit,
+    WrongSecondCheckDigit,
+    RepeatedDigits,
+    TooShort,
+    TooLong,
+    InvalidMask,
+    LettersMixedIn,
+}
+
+impl DocumentDefect {
+    const ALL: [DocumentDefect; 7] = [
+        DocumentDefect::WrongFirstCheckDigit,
+        DocumentDefect::WrongSecondCheckDigit,
+        DocumentDefect::RepeatedDigits,
+        DocumentDefect::TooShort,
+        DocumentDefect::TooLong,
+        DocumentDefect::InvalidMask,
+        DocumentDefect::LettersMixedIn,
+    ];
+
+    /// Regra de `validar_cpf`/`validar_cnpj` que deve reprovar o documento com este defeito.
+    fn expected_failure(self) -> ValidationFailure {
+        match self {
+            DocumentDefect::WrongFirstCheckDigit => ValidationFailure::FirstCheckDigitMismatch,
+            DocumentDefect::WrongSecondCheckDigit => ValidationFailure::SecondCheckDigitMismatch,
+            DocumentDefect::RepeatedDigits => ValidationFailure::RepeatedDigits,
+            DocumentDefect::TooShort | DocumentDefect::TooLong => ValidationFailure::WrongLength,
+            DocumentDefect::InvalidMask => ValidationFailure::InvalidMask,
+            DocumentDefect::LettersMixedIn => ValidationFailure::InvalidCharacters,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentoInvalido {
+    pub tipo: TipoDocumento,
+    pub valor: String,
+    pub defeito: DocumentDefect,
+    pub expected_failure: ValidationFailure,
+}
+
+fn mascarar_documento(tipo: TipoDocumento, documento: &str) -> String {
+    match tipo {
+        TipoDocumento::Cpf => formatar_cpf(documento),
+        TipoDocumento::Cnpj => formatar_cnpj(documento),
+    }
+}
+
+fn validar_documento(tipo: TipoDocumento, documento: &str) -> ValidationReport {
+    match tipo {
+        TipoDocumento::Cpf => validar_cpf(documento),
+        TipoDocumento::Cnpj => validar_cnpj(documento),
+    }
+}
+
+/// Segundo DV calculado sobre o corpo do documento já acrescido do primeiro DV.
+fn calcular_segundo_digito(tipo: TipoDocumento, corpo: &str) -> u32 {
+    match tipo {
+        TipoDocumento::Cpf => {
+            let digits: Vec<u32> = corpo.chars().filter_map(|c| c.to_digit(10)).collect();
+            calculate_digit(&digits, 11)
+        }
+        TipoDocumento::Cnpj => {
+            let values: Vec<u32> = corpo.chars().filter_map(cnpj_char_value).collect();
+            calculate_cnpj_digit(&values, &CNPJ_SECOND_WEIGHTS)
+        }
+    }
+}
+
+impl GeradorDocumentos {
+    fn aplicar_defeito(
+        &mut self,
+        tipo: TipoDocumento,
+        defeito: DocumentDefect,
+        com_mascara: bool,
+    ) -> String {
+        let valido = match tipo {
+            TipoDocumento::Cpf => self.cpf(false, None),
+            TipoDocumento::Cnpj => self.cnpj(false, false),
+        };
+        let n = valido.len();
+        let mascarar = |documento: String| {
+            if com_mascara {
+                mascarar_documento(tipo, &documento)
+            } else {
+                documento
+            }
+        };
+
+        match defeito {
+            DocumentDefect::WrongFirstCheckDigit => {
+                let correto = valido[n - 2..n - 1].parse::<u32>().unwrap_or(0);
+                let errado = (correto + self.rng.random_range(1..10)) % 10;
+                let corpo = format!("{}{}", &valido[..n - 2], errado);
+                let segundo = calcular_segundo_digito(tipo, &corpo);
+                mascarar(format!("{}{}", corpo, segundo))
+            }
+            DocumentDefect::WrongSecondCheckDigit => {
+                let correto = valido[n - 1..].parse::<u32>().unwrap_or(0);
+                let errado = (correto + self.rng.random_range(1..10)) % 10;
+                mascarar(format!("{}{}", &valido[..n - 1], errado))
+            }
+            DocumentDefect::RepeatedDigits => {
+                let digito = char::from(b'0' + self.rng.random_range(0..10u8));
+                mascarar(std::iter::repeat_n(digito, n).collect())
+            }
+            DocumentDefect::TooShort => {
+                let remover = self.rng.random_range(1..=3);
+                mascarar(valido[..n - remover].to_string())
+            }
+            DocumentDefect::TooLong => {
+                let extras: String = (0..self.rng.random_range(1..=3))
+                    .map(|_| char::from(b'0' + self.rng.random_range(0..10u8)))
+                    .collect();
+                format!("{}{}", mascarar(valido), extras)
+            }
+            // Máscara errada só existe em documento formatado: ignora `com_mascara`.
+            DocumentDefect::InvalidMask => {
+                let mut chars: Vec<char> = mascarar_documento(tipo, &valido).chars().collect();
+                let separadores: Vec<usize> = (0..chars.len())
+                    .filter(|&i| !chars[i].is_ascii_alphanumeric())
+                    .collect();
+                let posicao = separadores[self.rng.random_range(0..separadores.len())];
+                // Pontuação parcial ou fora de ordem é aceita; só reprova o que não é separador.
+                let trocas = [',', ';', ':', '_', '*'];
+                chars[posicao] = trocas[self.rng.random_range(0..trocas.len())];
+                chars.into_iter().collect()
+            }
+            DocumentDefect::LettersMixedIn => {
+                // No CNPJ alfanumérico letras são válidas na raiz; só os DVs precisam ser dígitos.
+                let posicao = match tipo {
+                    TipoDocumento::Cpf => self.rng.random_range(0..n),
+                    TipoDocumento::Cnpj => self.rng.random_range(n - 2..n),
+                };
+                let letra = char::from(b'A' + self.rng.random_range(0..26u8));
+                let mut documento = valido;
+                documento.replace_range(posicao..posicao + 1, &letra.to_string());
+                mascarar(documento)
+            }
+        }
+    }
+
+    /// Gera um documento que falha exatamente na regra associada ao defeito.
+    pub fn invalido(
+        &mut self,
+        tipo: TipoDocumento,
+        defeito: DocumentDefect,
+        com_mascara: bool,
+    ) -> DocumentoInvalido {
+        let expected_failure = defeito.expected_failure();
+
+        // Raríssimo, mas um sorteio pode cair em outra regra (ex.: DV errado formando
+        // dígitos todos iguais); nesse caso sorteia de novo.
+        let valor = loop {
+            let valor = self.aplicar_defeito(tipo, defeito, com_mascara);
+            if validar_documento(tipo, &valor).failure == Some(expected_failure) {
+                break valor;
+            }
+        };
+
+        DocumentoInvalido {
+            tipo,
+            valor,
+            defeito,
+            expected_failure,
+        }
+    }
+}
+
+/// Gera um documento inválido para cada defeito pedido (todos, se `defeitos` for omitido).
+/// `com_mascara` vale para todos os defeitos exceto `InvalidMask`, que sempre sai formatado.
+pub fn gerar_documentos_invalidos(
+    tipo: TipoDocumento,
+    defeitos: Option<Vec<DocumentDefect>>,
+    com_mascara: bool,
+    semente: Option<u64>,
+) -> Vec<DocumentoInvalido> {
+    let mut gerador = GeradorDocumentos::new(semente);
+
+    defeitos
+        .unwrap_or_else(|| DocumentDefect::ALL.to_vec())
+        .into_iter()
+        .map(|defeito| gerador.invalido(tipo, defeito, com_mascara))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aceita_cpf_e_cnpj_validos_conhecidos() {
+        assert!(validar_cpf("529.982.247-25").valid);
+        assert!(validar_cpf("52998224725").valid);
+        assert!(validar_cnpj("11.222.333/0001-81").valid);
+        assert!(validar_cnpj("11222333000181").valid);
+        assert!(validar_cnpj("12.ABC.345/01DE-35").valid);
+        assert!(validar_cnpj("12abc34501de35").valid);
+    }
+
+    #[test]
+    fn reprova_cada_digito_verificador_errado() {
+        let casos: [(ValidationReport, ValidationFailure); 6] = [
+            (
+                validar_cpf("529.982.247-35"),
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                validar_cpf("529.982.247-24"),
+                ValidationFailure::SecondCheckDigitMismatch,
+            ),
+            (
+                validar_cnpj("11.222.333/0001-91"),
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                validar_cnpj("11.222.333/0001-82"),
+                ValidationFailure::SecondCheckDigitMismatch,
+            ),
+            (
+                validar_cnpj("12.ABC.345/01DE-45"),
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                validar_cnpj("12.ABC.345/01DE-36"),
+                ValidationFailure::SecondCheckDigitMismatch,
+            ),
+        ];
+        for (report, falha) in casos {
+            assert!(!report.valid, "{}", report.normalized);
+            assert_eq!(report.failure, Some(falha), "{}", report.normalized);
+        }
+    }
+
+    #[test]
+    fn documentos_gerados_passam_na_validacao() {
+        let mut gerador = GeradorDocumentos::new(Some(1));
+        for _ in 0..200 {
+            for com_mascara in [false, true] {
+                let cpf = gerador.cpf(com_mascara, None);
+                assert!(validar_cpf(&cpf).valid, "{}", cpf);
+                for alfanumerico in [false, true] {
+                    let cnpj = gerador.cnpj(com_mascara, alfanumerico);
+                    assert!(validar_cnpj(&cnpj).valid, "{}", cnpj);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cnpj_alfanumerico_sorteia_letras_tambem_no_numero_de_ordem() {
+        let mut gerador = GeradorDocumentos::new(Some(7));
+        let ordens: Vec<String> = (0..50)
+            .map(|_| gerador.cnpj(false, true)[8..12].to_string())
+            .collect();
+        assert!(ordens
+            .iter()
+            .any(|ordem| ordem.chars().any(|c| c.is_ascii_uppercase())));
+        assert!(ordens.iter().all(|ordem| ordem != "0000"));
+
+        for semente in 0..50 {
+            let cnpj = gerar_cnpj(false, Some(true), None, Some(true), Some(semente)).unwrap();
+            assert!(validar_cnpj(&cnpj).valid, "{}", cnpj);
+            assert_ne!(&cnpj[8..12], "0001");
+        }
+    }
+
+    #[test]
+    fn relatorio_traz_digitos_mascara_e_regiao() {
+        let report = validar_cpf("52998224735");
+        assert_eq!(report.normalized, "52998224735");
+        assert_eq!(report.masked.as_deref(), Some("529.982.247-35"));
+        assert_eq!(report.expected_check_digits.as_deref(), Some("25"));
+        assert_eq!(report.supplied_check_digits.as_deref(), Some("35"));
+        assert!(!report.was_masked);
+        assert_eq!(report.likely_ufs, ["ES", "RJ"]);
+
+        let report = validar_cnpj("11.222.333/0001-82");
+        assert!(report.was_masked);
+        assert_eq!(report.expected_check_digits.as_deref(), Some("81"));
+        assert_eq!(report.supplied_check_digits.as_deref(), Some("82"));
+    }
+
+    #[test]
+    fn aceita_mascara_parcial_ou_fora_do_padrao() {
+        for cpf in [
+            "529982247-25",
+            "529.982.24725",
+            " 529 982 247 25 ",
+            "529.982.247.25",
+        ] {
+            let report = validar_cpf(cpf);
+            assert!(report.valid, "{}", cpf);
+            assert!(report.was_masked, "{}", cpf);
+            assert_eq!(report.normalized, "52998224725");
+        }
+        for cnpj in [
+            "11.222.333/0001-81 ",
+            "11222333/0001-81",
+            "11 222 333 0001 81",
+        ] {
+            assert!(validar_cnpj(cnpj).valid, "{}", cnpj);
+        }
+        assert!(validar_cnpj("12ABC345/01DE-35").valid);
+    }
+
+    #[test]
+    fn relatorio_indica_a_regra_que_falhou() {
+        let casos = [
+            (validar_cpf("529.982.247"), ValidationFailure::WrongLength),
+            (
+                validar_cpf("529.982.247-2A"),
+                ValidationFailure::InvalidCharacters,
+            ),
+            (
+                validar_cpf("529,982,247-25"),
+                ValidationFailure::InvalidMask,
+            ),
+            (
+                validar_cpf("111.111.111-11"),
+                ValidationFailure::RepeatedDigits,
+            ),
+            (
+                validar_cnpj("11.222.333/0001"),
+                ValidationFailure::WrongLength,
+            ),
+            (
+                validar_cnpj("12.ABC.345/01DE-3X"),
+                ValidationFailure::InvalidCharacters,
+            ),
+            (
+                validar_cnpj("11.222.333|0001-81"),
+                ValidationFailure::InvalidMask,
+            ),
+            (
+                validar_cnpj("00.000.000/0000-00"),
+                ValidationFailure::RepeatedDigits,
+            ),
+        ];
+        for (report, falha) in casos {
+            assert_eq!(report.failure, Some(falha), "{}", report.normalized);
+        }
+    }
+
+    #[test]
+    fn cpf_por_uf_usa_o_digito_da_regiao_fiscal() {
+        assert_eq!(regiao_fiscal_da_uf("sp"), Ok(8));
+        assert_eq!(regiao_fiscal_da_uf(" RS "), Ok(0));
+        assert_eq!(regiao_fiscal_da_uf("SC"), Ok(9));
+        assert!(regiao_fiscal_da_uf("XX").is_err());
+
+        for semente in 0..50 {
+            let cpf = gerar_cpf(false, Some("MG"), Some(semente)).unwrap();
+            assert_eq!(&cpf[8..9], "6");
+            let report = validar_cpf(&cpf);
+            assert!(report.valid, "{}", cpf);
+            assert_eq!(report.likely_ufs, ["MG"]);
+        }
+        assert!(gerar_cpf(false, Some("XX"), None).is_err());
+    }
+
+    #[test]
+    fn cnpj_com_filial_informada_ou_sorteada() {
+        let cnpj = gerar_cnpj(true, None, Some(42), None, Some(1)).unwrap();
+        assert_eq!(&cnpj[11..15], "0042");
+        assert!(validar_cnpj(&cnpj).valid);
+
+        let matriz = gerar_cnpj(false, None, None, None, Some(1)).unwrap();
+        assert_eq!(&matriz[8..12], "0001");
+
+        for semente in 0..50 {
+            let cnpj = gerar_cnpj(false, None, None, Some(true), Some(semente)).unwrap();
+            let filial: u32 = cnpj[8..12].parse().unwrap();
+            assert!((2..=MAX_FILIAL).contains(&filial));
+            assert!(validar_cnpj(&cnpj).valid);
+        }
+
+        assert!(gerar_cnpj(false, None, Some(0), None, None).is_err());
+        assert!(gerar_cnpj(false, None, Some(MAX_FILIAL + 1), None, None).is_err());
+        assert!(gerar_cnpj(false, None, Some(42), Some(true), None).is_err());
+        assert!(gerar_cnpj(false, Some(true), Some(42), Some(true), None).is_err());
+        assert!(gerar_cnpj(false, None, Some(42), Some(false), None).is_ok());
+    }
+
+    #[test]
+    fn familia_compartilha_a_raiz() {
+        let familia = gerar_familia_cnpj(false, Some(true), 3, Some(5)).unwrap();
+        assert_eq!(
+            familia.matriz,
+            format!("{}0001{}", familia.raiz, &familia.matriz[12..])
+        );
+        assert_eq!(familia.filiais.len(), 3);
+        for (i, filial) in familia.filiais.iter().enumerate() {
+            assert_eq!(filial[8..12], format!("{:04}", i + 2));
+            assert!(validar_cnpj(filial).valid, "{}", filial);
+            assert_eq!(mesma_raiz_cnpj(&familia.matriz, filial), Ok(true));
+        }
+        assert_eq!(
+            mesma_raiz_cnpj("11.222.333/0001-81", "11222334000181"),
+            Ok(false)
+        );
+        assert!(mesma_raiz_cnpj("11.222.333/0001", "11222333000181").is_err());
+        assert!(gerar_familia_cnpj(false, None, MAX_FILIAL, None).is_err());
+    }
+
+    #[test]
+    fn mesma_semente_gera_a_mesma_sequencia() {
+        let sequencia = |semente| {
+            let mut gerador = GeradorDocumentos::new(Some(semente));
+            (0..20)
+                .map(|_| format!("{} {}", gerador.cpf(false, None), gerador.cnpj(false, true)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sequencia(42), sequencia(42));
+        assert_ne!(sequencia(42), sequencia(43));
+
+        assert_eq!(
+            gerar_cpf(true, None, Some(9)).unwrap(),
+            gerar_cpf(true, None, Some(9)).unwrap()
+        );
+        assert_eq!(
+            gerar_familia_cnpj(false, None, 2, Some(9)).unwrap().filiais,
+            gerar_familia_cnpj(false, None, 2, Some(9)).unwrap().filiais
+        );
+    }
+
+    #[test]
+    fn cada_defeito_reprova_na_regra_esperada() {
+        for tipo in [TipoDocumento::Cpf, TipoDocumento::Cnpj] {
+            for com_mascara in [false, true] {
+                for documento in gerar_documentos_invalidos(tipo, None, com_mascara, Some(11)) {
+                    let report = validar_documento(tipo, &documento.valor);
+                    assert_eq!(
+                        report.failure,
+                        Some(documento.expected_failure),
+                        "{}",
+                        documento.valor
+                    );
+                    let formatado = documento.valor.contains(['.', '-', '/']);
+                    if documento.defeito == DocumentDefect::InvalidMask {
+                        assert!(formatado, "{}", documento.valor);
+                    } else {
+                        assert_eq!(formatado, com_mascara, "{}", documento.valor);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn formata_enquanto_digita_e_normaliza() {
+        let parcial = formatar_documento("5299822", None);
+        assert_eq!(parcial.tipo, TipoDocumento::Cpf);
+        assert_eq!(parcial.valor, "529.982.2");
+        assert!(!parcial.completo);
+
+        let cpf = formatar_documento("529982247259999", Some(TipoDocumento::Cpf));
+        assert_eq!(cpf.valor, "529.982.247-25");
+        assert!(cpf.completo);
+
+        let cnpj = formatar_documento("12abc34501de35", None);
+        assert_eq!(cnpj.tipo, TipoDocumento::Cnpj);
+        assert_eq!(cnpj.valor, "12.ABC.345/01DE-35");
+        assert_eq!(cnpj.normalizado, "12ABC34501DE35");
+
+        assert_eq!(
+            formatar_documento("112223330001", None).valor,
+            "11.222.333/0001"
+        );
+        assert_eq!(normalizar_documento("529.982.247-25", None), "52998224725");
+        assert_eq!(
+            normalizar_documento("12.abc.345/01de-35", None),
+            "12ABC34501DE35"
+        );
+        assert_eq!(
+            normalizar_documento("529.982.247-2a", Some(TipoDocumento::Cpf)),
+            "5299822472"
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use crate::cpf_cnpj::GeradorDocumentos;
+use crate::validacao::{mascara_valida, ValidationFailure, ValidationReport};
+use rand::Rng;
+
+const PIS_WEIGHTS: [u32; 10] = [3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+const RENAVAM_WEIGHTS: [u32; 10] = [3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+
+/// UFs do título de eleitor, indexadas pelo código de 01 a 28 (28 = exterior).
+const TITULO_UFS: [&str; 28] = [
+    "SP", "MG", "RJ", "RS", "BA", "PR", "CE", "PE", "SC", "GO", "MA", "PB", "PA", "ES", "PI", "RN",
+    "AL", "MT", "MS", "DF", "SE", "AM", "RO", "AC", "AP", "RR", "TO", "ZZ",
+];
+
+fn para_string(digits: &[u32]) -> String {
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+fn sortear_digitos<R: Rng + ?Sized>(rng: &mut R, quantidade: usize) -> Vec<u32> {
+    (0..quantidade).map(|_| rng.random_range(0..10)).collect()
+}
+
+/// Regras comuns a todos os documentos numéricos: só dígitos, tamanho exato, máscara
+/// canônica (ou nenhuma) e dígitos não todos iguais. Os dígitos só são devolvidos se o
+/// formato passar.
+fn validar_formato(
+    input: &str,
+    tamanho: usize,
+    mascarar: fn(&str) -> String,
+) -> (ValidationReport, Option<Vec<u32>>) {
+    let normalized: String = input.chars().filter(|c| c.is_ascii_digit()).collect();
+    let mut report = ValidationReport::new(input, normalized.clone());
+
+    if input.chars().any(|c| c.is_ascii_alphabetic()) {
+        return (report.fail(ValidationFailure::InvalidCharacters), None);
+    }
+
+    if normalized.len() != tamanho {
+        return (report.fail(ValidationFailure::WrongLength), None);
+    }
+
+    let masked = mascarar(&normalized);
+    if !mascara_valida(input) {
+        return (report.fail(ValidationFailure::InvalidMask), None);
+    }
+    report.masked = Some(masked);
+
+    let digits: Vec<u32> = normalized.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    if digits.windows(2).all(|w| w[0] == w[1]) {
+        return (report.fail(ValidationFailure::RepeatedDigits), None);
+    }
+
+    (report, Some(digits))
+}
+
+fn comparar_digitos(
+    report: ValidationReport,
+    esperado: &[u32],
+    informado: &[u32],
+) -> ValidationReport {
+    let mut report = report;
+    report.expected_check_digits = Some(para_string(esperado));
+    report.supplied_check_digits = Some(para_string(informado));
+
+    if esperado.first() != informado.first() {
+        return report.fail(ValidationFailure::FirstCheckDigitMismatch);
+    }
+
+    if esperado.get(1) != informado.get(1) {
+        return report.fail(ValidationFailure::SecondCheckDigitMismatch);
+    }
+
+    report.pass()
+}
+
+// ---- PIS/PASEP/NIS ----
+
+fn calcular_digito_pis(base: &[u32]) -> u32 {
+    let sum: u32 = base
+        .iter()
+        .zip(PIS_WEIGHTS.iter())
+        .map(|(&d, &w)| d * w)
+        .sum();
+    let digito = 11 - sum % 11;
+    if digito >= 10 {
+        0
+    } else {
+        digito
+    }
+}
+
+fn formatar_pis(pis: &str) -> String {
+    format!(
+        "{}.{}.{}-{}",
+        &pis[0..3],
+        &pis[3..8],
+        &pis[8..10],
+        &pis[10..11]
+    )
+}
+
+pub fn gerar_pis(com_mascara: bool, semente: Option<u64>) -> String {
+    let mut pis = sortear_digitos(GeradorDocumentos::new(semente).rng(), 10);
+    pis.push(calcular_digito_pis(&pis));
+
+    let pis = para_string(&pis);
+    if com_mascara {
+        formatar_pis(&pis)
+    } else {
+        pis
+    }
+}
+
+pub fn validar_pis(pis_input: &str) -> ValidationReport {
+    let (report, digits) = validar_formato(pis_input, 11, formatar_pis);
+    let Some(digits) = digits else {
+        return report;
+    };
+
+    comparar_digitos(report, &[calcular_digito_pis(&digits[..10])], &digits[10..])
+}
+
+// ---- CNH ----
+
+/// Dígitos verificadores da CNH. Quando o primeiro resto é 10, o segundo dígito recebe um
+/// desconto de 2; se isso o deixar negativo, nenhuma CNH com essa base é válida.
+fn calcular_digitos_cnh(base: &[u32]) -> Option<(u32, u32)> {
+    let sum: u32 = base.iter().zip((1..=9).rev()).map(|(&d, w)| d * w).sum();
+    let (first_digit, desconto) = match sum % 11 {
+        resto if resto >= 10 => (0, 2),
+        resto => (resto, 0),
+    };
+
+    let sum: u32 = base.iter().zip(1..=9).map(|(&d, w)| d * w).sum();
+    let second_digit = match sum % 11 {
+        resto if resto >= 10 => 0,
+        resto => resto.checked_sub(desconto)?,
+    };
+
+    Some((first_digit, second_digit))
+}
+
+pub fn gerar_cnh(semente: Option<u64>) -> String {
+    let mut gerador = GeradorDocumentos::new(semente);
+
+    loop {
+        let base = sortear_digitos(gerador.rng(), 9);
+        if base.windows(2).all(|w| w[0] == w[1]) {
+            continue;
+        }
+        if let Some((first_digit, second_digit)) = calcular_digitos_cnh(&base) {
+            return format!("{}{}{}", para_string(&base), first_digit, second_digit);
+        }
+    }
+}
+
+pub fn validar_cnh(cnh_input: &str) -> ValidationReport {
+    let (report, digits) = validar_formato(cnh_input, 11, |cnh| cnh.to_string());
+    let Some(digits) = digits else {
+        return report;
+    };
+
+    match calcular_digitos_cnh(&digits[..9]) {
+        Some((first_digit, second_digit)) => {
+            comparar_digitos(report, &[first_digit, second_digit], &digits[9..])
+        }
+        None => report.fail(ValidationFailure::SecondCheckDigitMismatch),
+    }
+}
+
+// ---- RENAVAM ----
+
+fn calcular_digito_renavam(base: &[u32]) -> u32 {
+    let sum: u32 = base
+        .iter()
+        .zip(RENAVAM_WEIGHTS.iter())
+        .map(|(&d, &w)| d * w)
+        .sum();
+    let digito = (sum * 10) % 11;
+    if digito == 10 {
+        0
+    } else {
+        digito
+    }
+}
+
+pub(crate) fn sortear_renavam<R: Rng + ?Sized>(rng: &mut R) -> String {
+    let mut renavam = sortear_digitos(rng, 10);
+    renavam.push(calcular_digito_renavam(&renavam));
+    para_string(&renavam)
+}
+
+pub fn gerar_renavam(semente: Option<u64>) -> String {
+    sortear_renavam(GeradorDocumentos::new(semente).rng())
+}
+
+/// Aceita o RENAVAM atual (11 dígitos) e o antigo (9 dígitos, completado com zeros à esquerda).
+pub fn validar_renavam(renavam_input: &str) -> ValidationReport {
+    let trimmed = renavam_input.trim();
+    let input = if trimmed.len() == 9 && trimmed.chars().all(|c| c.is_ascii_digit()) {
+        format!("00{}", trimmed)
+    } else {
+        renavam_input.to_string()
+    };
+
+    let (report, digits) = validar_formato(&input, 11, |renavam| renavam.to_string());
+    let Some(digits) = digits else {
+        return report;
+    };
+
+    comparar_digitos(
+        report,
+        &[calcular_digito_renavam(&digits[..10])],
+        &digits[10..],
+    )
+}
+
+// ---- Título de Eleitor ----
+
+fn codigo_uf_titulo(uf: &str) -> Result<u32, String> {
+    let uf = uf.trim().to_ascii_uppercase();
+    TITULO_UFS
+        .iter()
+        .position(|&codigo| codigo == uf)
+        .map(|posicao| posicao as u32 + 1)
+        .ok_or_else(|| format!("UF desconhecida: {}", uf))
+}
+
+/// Em SP (01) e MG (02), resto 0 vira dígito 1; nas demais UFs, resto 10 vira 0.
+fn ajustar_digito_titulo(resto: u32, codigo_uf: u32) -> u32 {
+    match resto {
+        10 => 0,
+        0 if codigo_uf <= 2 => 1,
+        resto => resto,
+    }
+}
+
+fn calcular_digitos_titulo(sequencial: &[u32], uf: &[u32]) -> (u32, u32) {
+    let codigo_uf = uf[0] * 10 + uf[1];
+
+    let sum: u32 = sequencial.iter().zip(2..=9).map(|(&d, w)| d * w).sum();
+    let first_digit = ajustar_digito_titulo(sum % 11, codigo_uf);
+
+    let sum = uf[0] * 7 + uf[1] * 8 + first_digit * 9;
+    let second_digit = ajustar_digito_titulo(sum % 11, codigo_uf);
+
+    (first_digit, second_digit)
+}
+
+fn formatar_titulo(titulo: &str) -> String {
+    format!("{} {} {}", &titulo[0..4], &titulo[4..8], &titulo[8..12])
+}
+
+pub fn gerar_titulo_eleitor(
+    com_mascara: bool,
+    uf: Option<&str>,
+    semente: Option<u64>,
+) -> Result<String, String> {
+    let mut gerador = GeradorDocumentos::new(semente);
+
+    let codigo_uf = match uf {
+        Some(uf) => codigo_uf_titulo(uf)?,
+        None => gerador.rng().random_range(1..=TITULO_UFS.len() as u32),
+    };
+
+    let mut titulo = sortear_digitos(gerador.rng(), 8);
+    titulo.extend_from_slice(&[codigo_uf / 10, codigo_uf % 10]);
+
+    let (first_digit, second_digit) = calcular_digitos_titulo(&titulo[..8], &titulo[8..10]);
+    titulo.extend_from_slice(&[first_digit, second_digit]);
+
+    let titulo = para_string(&titulo);
+    Ok(if com_mascara {
+        formatar_titulo(&titulo)
+    } else {
+        titulo
+    })
+}
+
+pub fn validar_titulo_eleitor(titulo_input: &str) -> ValidationReport {
+    let (mut report, digits) = validar_formato(titulo_input, 12, formatar_titulo);
+    let Some(digits) = digits else {
+        return report;
+    };
+
+    let codigo_uf = (digits[8] * 10 + digits[9]) as usize;
+    if !(1..=TITULO_UFS.len()).contains(&codigo_uf) {
+        return report.fail(ValidationFailure::InvalidUfCode);
+    }
+    report.likely_ufs = vec![TITULO_UFS[codigo_uf - 1].to_string()];
+
+    let (first_digit, second_digit) = calcular_digitos_titulo(&digits[..8], &digits[8..10]);
+    comparar_digitos(report, &[first_digit, second_digit], &digits[10..])
+}
+
+// ---- Cartão Nacional de Saúde ----
+
+fn soma_cns(digits: &[u32]) -> u32 {
+    digits.iter().zip((1..=15).rev()).map(|(&d, w)| d * w).sum()
+}
+
+fn formatar_cns(cns: &str) -> String {
+    format!(
+        "{} {} {} {}",
+        &cns[0..3],
+        &cns[3..7],
+        &cns[7..11],
+        &cns[11..15]
+    )
+}
+
+/// CNS definitivo (inicia com 1 ou 2, derivado de um PIS) ou provisório (inicia com 7, 8 ou 9).
+pub fn gerar_cns(com_mascara: bool, provisorio: Option<bool>, semente: Option<u64>) -> String {
+    let mut gerador = GeradorDocumentos::new(semente);
+    let rng = gerador.rng();
+
+    let cns = if provisorio.unwrap_or(false) {
+        loop {
+            let mut cns = vec![rng.random_range(7..=9)];
+            cns.extend(sortear_digitos(rng, 13));
+            let digito = (11 - soma_cns(&cns) % 11) % 11;
+            if digito < 10 {
+                cns.push(digito);
+                break cns;
+            }
+        }
+    } else {
+        let mut cns = vec![rng.random_range(1..=2)];
+        cns.extend(sortear_digitos(rng, 10));
+
+        let sum = soma_cns(&cns);
+        let digito = (11 - sum % 11) % 11;
+        if digito == 10 {
+            cns.extend_from_slice(&[0, 0, 1]);
+            cns.push((11 - (sum + 2) % 11) % 11);
+        } else {
+            cns.extend_from_slice(&[0, 0, 0]);
+            cns.push(digito);
+        }
+        cns
+    };
+
+    let cns = para_string(&cns);
+    if com_mascara {
+        formatar_cns(&cns)
+    } else {
+        cns
+    }
+}
+
+pub fn validar_cns(cns_input: &str) -> ValidationReport {
+    let (report, digits) = validar_formato(cns_input, 15, formatar_cns);
+    let Some(digits) = digits else {
+        return report;
+    };
+
+    if !matches!(digits[0], 1 | 2 | 7 | 8 | 9) {
+        return report.fail(ValidationFailure::InvalidPrefix);
+    }
+
+    if !soma_cns(&digits).is_multiple_of(11) {
+        return report.fail(ValidationFailure::ChecksumMismatch);
+    }
+
+    report.pass()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aceita_documentos_validos_conhecidos() {
+        assert!(validar_pis("120.56492.58-1").valid);
+        assert!(validar_pis("12056492581").valid);
+        assert!(validar_cnh("02650306461").valid);
+        assert!(validar_cnh("62472927637").valid);
+        assert!(validar_renavam("00639884962").valid);
+        assert!(validar_renavam("639884962").valid);
+        assert!(validar_titulo_eleitor("1023 8501 0671").valid);
+        assert!(validar_titulo_eleitor("004356870906").valid);
+        assert!(validar_cns("702 0028 8742 9583").valid);
+        assert!(validar_cns("898001160660001").valid);
+    }
+
+    #[test]
+    fn reprova_cada_digito_verificador_errado() {
+        use ValidationFailure::*;
+
+        assert_eq!(
+            validar_pis("120.56492.58-2").failure,
+            Some(FirstCheckDigitMismatch)
+        );
+        assert_eq!(
+            validar_cnh("02650306471").failure,
+            Some(FirstCheckDigitMismatch)
+        );
+        assert_eq!(
+            validar_cnh("02650306462").failure,
+            Some(SecondCheckDigitMismatch)
+        );
+        assert_eq!(
+            validar_renavam("00639884963").failure,
+            Some(FirstCheckDigitMismatch)
+        );
+        assert_eq!(
+            validar_titulo_eleitor("102385010681").failure,
+            Some(FirstCheckDigitMismatch)
+        );
+        assert_eq!(
+            validar_titulo_eleitor("102385010672").failure,
+            Some(SecondCheckDigitMismatch)
+        );
+        assert_eq!(
+            validar_cns("702002887429584").failure,
+            Some(ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn reprova_formato_uf_e_prefixo() {
+        use ValidationFailure::*;
+
+        assert_eq!(validar_pis("1205649258").failure, Some(WrongLength));
+        assert_eq!(validar_pis("120*5649*258-1").failure, Some(InvalidMask));
+        assert_eq!(validar_cnh("0265030646A").failure, Some(InvalidCharacters));
+        assert_eq!(validar_renavam("11111111111").failure, Some(RepeatedDigits));
+        assert_eq!(
+            validar_titulo_eleitor("123456782991").failure,
+            Some(InvalidUfCode)
+        );
+        assert_eq!(validar_cns("302002887429583").failure, Some(InvalidPrefix));
+    }
+
+    #[test]
+    fn documentos_gerados_passam_na_validacao() {
+        for semente in 0..200 {
+            let semente = Some(semente);
+            for com_mascara in [false, true] {
+                let pis = gerar_pis(com_mascara, semente);
+                assert!(validar_pis(&pis).valid, "{}", pis);
+                let titulo = gerar_titulo_eleitor(com_mascara, None, semente).unwrap();
+                assert!(validar_titulo_eleitor(&titulo).valid, "{}", titulo);
+                for provisorio in [false, true] {
+                    let cns = gerar_cns(com_mascara, Some(provisorio), semente);
+                    assert!(validar_cns(&cns).valid, "{}", cns);
+                }
+            }
+            let cnh = gerar_cnh(semente);
+            assert!(validar_cnh(&cnh).valid, "{}", cnh);
+            let renavam = gerar_renavam(semente);
+            assert!(validar_renavam(&renavam).valid, "{}", renavam);
+        }
+
+        let titulo = gerar_titulo_eleitor(false, Some("mg"), Some(1)).unwrap();
+        assert_eq!(validar_titulo_eleitor(&titulo).likely_ufs, ["MG"]);
+        assert!(gerar_titulo_eleitor(false, Some("XX"), None).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::cpf_cnpj::{validar_cnpj, validar_cpf, TipoDocumento};
+use crate::validacao::ValidationReport;
+use serde::{Deserialize, Serialize};
+
+/// Padrões procurados no texto: `D` é um dígito e `A` um dígito ou letra maiúscula
+/// (CNPJ alfanumérico). Os mascarados vêm antes para terem prioridade. Padrões com o
+/// terceiro campo só contam quando os DVs conferem: sem máscara, qualquer SKU ou token
+/// de 14 caracteres terminado em dois dígitos pareceria um CNPJ alfanumérico inválido.
+const PADROES: [(&str, TipoDocumento, bool); 5] = [
+    ("AA.AAA.AAA/AAAA-DD", TipoDocumento::Cnpj, false),
+    ("DDD.DDD.DDD-DD", TipoDocumento::Cpf, false),
+    ("AAAAAAAAAAAADD", TipoDocumento::Cnpj, true),
+    ("DDDDDDDDDDDDDD", TipoDocumento::Cnpj, false),
+    ("DDDDDDDDDDD", TipoDocumento::Cpf, false),
+];
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentoEncontrado {
+    pub tipo: TipoDocumento,
+    pub valor: String,
+    /// Posição em caracteres (não bytes) do início e do fim (exclusivo) do trecho.
+    pub inicio: usize,
+    pub fim: usize,
+    pub linha: usize,
+    pub validacao: ValidationReport,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumoExtracao {
+    pub total: usize,
+    pub validos: usize,
+    pub invalidos: usize,
+    pub cpfs: usize,
+    pub cnpjs: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultadoExtracao {
+    pub documentos: Vec<DocumentoEncontrado>,
+    pub resumo: ResumoExtracao,
+}
+
+fn casa_padrao(padrao: &str, trecho: &[char]) -> bool {
+    padrao.len() == trecho.len()
+        && padrao.chars().zip(trecho).all(|(p, &c)| match p {
+            'D' => c.is_ascii_digit(),
+            'A' => c.is_ascii_digit() || c.is_ascii_uppercase(),
+            p => p == c,
+        })
+}
+
+/// Procura CPFs/CNPJs, mascarados ou não, delimitados por caracteres não alfanuméricos.
+pub fn encontrar_documentos(texto: &str) -> Vec<DocumentoEncontrado> {
+    let chars: Vec<char> = texto.chars().collect();
+    let mut documentos = Vec::new();
+    let mut linha = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let inicio_de_palavra = i == 0 || !chars[i - 1].is_alphanumeric();
+
+        let encontrado = if inicio_de_palavra {
+            PADROES.iter().find_map(|&(padrao, tipo, so_validos)| {
+                let fim = i + padrao.len();
+                if fim > chars.len()
+                    || !casa_padrao(padrao, &chars[i..fim])
+                    || chars.get(fim).is_some_and(|c| c.is_alphanumeric())
+                {
+                    return None;
+                }
+                let valor: String = chars[i..fim].iter().collect();
+                let validacao = match tipo {
+                    TipoDocumento::Cpf => validar_cpf(&valor),
+                    TipoDocumento::Cnpj => validar_cnpj(&valor),
+                };
+                (!so_validos || validacao.valid).then_some((tipo, valor, fim, validacao))
+            })
+        } else {
+            None
+        };
+
+        match encontrado {
+            Some((tipo, valor, fim, validacao)) => {
+                documentos.push(DocumentoEncontrado {
+                    tipo,
+                    valor,
+                    inicio: i,
+                    fim,
+                    linha,
+                    validacao,
+                });
+                i = fim;
+            }
+            None => {
+                if chars[i] == '\n' {
+                    linha += 1;
+                }
+                i += 1;
+            }
+        }
+    }
+
+    documentos
+}
+
+fn resumir(documentos: &[DocumentoEncontrado]) -> ResumoExtracao {
+    documentos
+        .iter()
+        .fold(ResumoExtracao::default(), |mut resumo, documento| {
+            resumo.total += 1;
+            if documento.validacao.valid {
+                resumo.validos += 1;
+            } else {
+                resumo.invalidos += 1;
+            }
+            match documento.tipo {
+                TipoDocumento::Cpf => resumo.cpfs += 1,
+                TipoDocumento::Cnpj => resumo.cnpjs += 1,
+            }
+            resumo
+        })
+}
+
+/// Lê o texto informado ou, se vier `caminho`, o conteúdo do arquivo.
+pub fn ler_texto_ou_arquivo(
+    texto: Option<String>,
+    caminho: Option<String>,
+) -> Result<String, String> {
+    match (texto, caminho) {
+        (Some(texto), None) => Ok(texto),
+        (None, Some(caminho)) => std::fs::read(&caminho)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .map_err(|e| format!("Falha ao ler {}: {}", caminho, e)),
+        _ => Err("Informe o texto ou o caminho de um arquivo, mas não ambos".into()),
+    }
+}
+
+/// Encontra e valida todos os CPFs/CNPJs de um texto.
+pub fn extrair_documentos(texto: &str) -> ResultadoExtracao {
+    let documentos = encontrar_documentos(texto);
+    let resumo = resumir(&documentos);
+    ResultadoExtracao { documentos, resumo }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encontra_documentos_mascarados_e_sem_mascara() {
+        let texto = "Cliente 529.982.247-25, CNPJ 11.222.333/0001-81\n\
+                     alfa 12.ABC.345/01DE-35 e 12ABC34501DE35; numérico 11222333000181 e 52998224725";
+        let documentos = encontrar_documentos(texto);
+        let valores: Vec<&str> = documentos.iter().map(|d| d.valor.as_str()).collect();
+        assert_eq!(
+            valores,
+            [
+                "529.982.247-25",
+                "11.222.333/0001-81",
+                "12.ABC.345/01DE-35",
+                "12ABC34501DE35",
+                "11222333000181",
+                "52998224725",
+            ]
+        );
+        assert!(documentos.iter().all(|d| d.validacao.valid));
+        assert_eq!(documentos[2].tipo, TipoDocumento::Cnpj);
+        assert_eq!(documentos[3].tipo, TipoDocumento::Cnpj);
+        assert_eq!(documentos[3].linha, 2);
+        let inicio = texto.chars().position(|c| c == '5').unwrap();
+        assert_eq!(
+            (documentos[0].inicio, documentos[0].fim),
+            (inicio, inicio + 14)
+        );
+    }
+
+    #[test]
+    fn ignora_trechos_colados_em_outras_palavras() {
+        assert!(encontrar_documentos("id52998224725 x529.982.247-25").is_empty());
+        assert!(encontrar_documentos("112223330001810").is_empty());
+    }
+
+    #[test]
+    fn ignora_tokens_alfanumericos_que_nao_sao_cnpj() {
+        let texto = "SKU ABCDEFGHIJKL12, pedido PED2024XYZ9A77 e alfa 12ABC34501DE36";
+        let resultado = extrair_documentos(texto);
+        assert!(resultado.documentos.is_empty());
+        assert_eq!(resultado.resumo.invalidos, 0);
+
+        // Só dígitos continua sendo reportado mesmo com DV errado.
+        let resultado = extrair_documentos("11222333000182");
+        assert_eq!(resultado.resumo.invalidos, 1);
+        assert_eq!(resultado.documentos[0].tipo, TipoDocumento::Cnpj);
+    }
+
+    #[test]
+    fn resumo_conta_validos_e_invalidos() {
+        let resultado = extrair_documentos("529.982.247-24 11.222.333/0001-81 12ABC34501DE35");
+        assert_eq!(resultado.resumo.total, 3);
+        assert_eq!(resultado.resumo.validos, 2);
+        assert_eq!(resultado.resumo.invalidos, 1);
+        assert_eq!(resultado.resumo.cpfs, 1);
+        assert_eq!(resultado.resumo.cnpjs, 2);
+    }
+}
@@ -0,0 +1,562 @@
+use crate::cpf_cnpj::GeradorDocumentos;
+use crate::validacao::{ValidationFailure, ValidationReport};
+use rand::Rng;
+
+/// Formato de inscrição estadual de uma UF. Algumas UFs aceitam mais de um tamanho
+/// (ex.: BA com 8 ou 9 dígitos); a primeira regra da lista é a usada na geração.
+struct Regra {
+    tamanho: usize,
+    /// Posições dos dígitos verificadores, da esquerda para a direita.
+    posicoes_dv: &'static [usize],
+    /// Prefixos aceitos, em que `#` aceita qualquer dígito. Vazio = sem restrição.
+    prefixos: &'static [&'static str],
+    mascara: &'static str,
+    /// Calcula os DVs esperados a partir da inscrição completa, lendo só as posições que
+    /// não são DV.
+    calcular: fn(&[u32]) -> Vec<u32>,
+    produtor_rural: bool,
+}
+
+const PESOS_9_A_2: [u32; 8] = [9, 8, 7, 6, 5, 4, 3, 2];
+
+fn soma(digits: &[u32], pesos: &[u32]) -> u32 {
+    digits.iter().zip(pesos.iter()).map(|(&d, &p)| d * p).sum()
+}
+
+fn dv_mod11(soma: u32) -> u32 {
+    let resto = soma % 11;
+    if resto < 2 {
+        0
+    } else {
+        11 - resto
+    }
+}
+
+fn dv_mod10(soma: u32) -> u32 {
+    (10 - soma % 10) % 10
+}
+
+fn numero(digits: &[u32]) -> u32 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d)
+}
+
+fn dv_nove_digitos(d: &[u32]) -> Vec<u32> {
+    vec![dv_mod11(soma(&d[..8], &PESOS_9_A_2))]
+}
+
+fn dv_ac_df(d: &[u32]) -> Vec<u32> {
+    let mut corpo = d[..11].to_vec();
+    let first_digit = dv_mod11(soma(&corpo, &[4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]));
+    corpo.push(first_digit);
+    let second_digit = dv_mod11(soma(&corpo, &[5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]));
+    vec![first_digit, second_digit]
+}
+
+/// AP soma uma constante `p` e usa `d` quando o resto é 0, conforme a faixa da inscrição.
+fn dv_ap(d: &[u32]) -> Vec<u32> {
+    let (p, dv_especial) = match numero(&d[..8]) {
+        3_000_001..=3_017_000 => (5, 0),
+        3_017_001..=3_019_022 => (9, 1),
+        _ => (0, 0),
+    };
+    let digito = 11 - (p + soma(&d[..8], &PESOS_9_A_2)) % 11;
+    vec![match digito {
+        10 => 0,
+        11 => dv_especial,
+        digito => digito,
+    }]
+}
+
+/// BA calcula primeiro o último DV; o módulo (10 ou 11) depende do primeiro dígito
+/// (8 dígitos) ou do segundo (9 dígitos).
+fn dv_ba(d: &[u32]) -> Vec<u32> {
+    let corpo_len = d.len() - 2;
+    let digito_modulo = if d.len() == 8 { d[0] } else { d[1] };
+    let modulo: fn(u32) -> u32 = if matches!(digito_modulo, 6 | 7 | 9) {
+        dv_mod11
+    } else {
+        dv_mod10
+    };
+
+    let pesos: Vec<u32> = (2..=corpo_len as u32 + 2).rev().collect();
+    let mut corpo = d[..corpo_len].to_vec();
+    let second_digit = modulo(soma(&corpo, &pesos[1..]));
+    corpo.push(second_digit);
+    let first_digit = modulo(soma(&corpo, &pesos));
+    vec![first_digit, second_digit]
+}
+
+fn dv_go(d: &[u32]) -> Vec<u32> {
+    vec![match soma(&d[..8], &PESOS_9_A_2) % 11 {
+        0 => 0,
+        1 if (10_103_105..=10_119_997).contains(&numero(&d[..8])) => 1,
+        1 => 0,
+        resto => 11 - resto,
+    }]
+}
+
+fn dv_mt(d: &[u32]) -> Vec<u32> {
+    vec![dv_mod11(soma(&d[..10], &[3, 2, 9, 8, 7, 6, 5, 4, 3, 2]))]
+}
+
+/// MG: o primeiro DV insere um zero após o código do município, multiplica alternadamente
+/// por 1 e 2 e soma os algarismos dos produtos.
+fn dv_mg(d: &[u32]) -> Vec<u32> {
+    let mut com_zero = d[..3].to_vec();
+    com_zero.push(0);
+    com_zero.extend_from_slice(&d[3..11]);
+    let soma_algarismos: u32 = com_zero
+        .iter()
+        .enumerate()
+        .map(|(i, &digito)| digito * if i % 2 == 0 { 1 } else { 2 })
+        .map(|produto| produto / 10 + produto % 10)
+        .sum();
+    let first_digit = dv_mod10(soma_algarismos);
+
+    let mut corpo = d[..11].to_vec();
+    corpo.push(first_digit);
+    let second_digit = dv_mod11(soma(&corpo, &[3, 2, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2]));
+    vec![first_digit, second_digit]
+}
+
+fn dv_pr(d: &[u32]) -> Vec<u32> {
+    let mut corpo = d[..8].to_vec();
+    let first_digit = dv_mod11(soma(&corpo, &[3, 2, 7, 6, 5, 4, 3, 2]));
+    corpo.push(first_digit);
+    let second_digit = dv_mod11(soma(&corpo, &[4, 3, 2, 7, 6, 5, 4, 3, 2]));
+    vec![first_digit, second_digit]
+}
+
+fn dv_pe(d: &[u32]) -> Vec<u32> {
+    let mut corpo = d[..7].to_vec();
+    let first_digit = dv_mod11(soma(&corpo, &PESOS_9_A_2[1..]));
+    corpo.push(first_digit);
+    let second_digit = dv_mod11(soma(&corpo, &PESOS_9_A_2));
+    vec![first_digit, second_digit]
+}
+
+fn dv_rj(d: &[u32]) -> Vec<u32> {
+    vec![dv_mod11(soma(&d[..7], &[2, 7, 6, 5, 4, 3, 2]))]
+}
+
+fn dv_rn_dez_digitos(d: &[u32]) -> Vec<u32> {
+    vec![dv_mod11(soma(&d[..9], &[10, 9, 8, 7, 6, 5, 4, 3, 2]))]
+}
+
+fn dv_rs(d: &[u32]) -> Vec<u32> {
+    vec![dv_mod11(soma(&d[..9], &[2, 9, 8, 7, 6, 5, 4, 3, 2]))]
+}
+
+fn dv_ro(d: &[u32]) -> Vec<u32> {
+    let digito = 11 - soma(&d[..13], &[6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2]) % 11;
+    vec![if digito >= 10 { digito - 10 } else { digito }]
+}
+
+fn dv_rr(d: &[u32]) -> Vec<u32> {
+    vec![soma(&d[..8], &[1, 2, 3, 4, 5, 6, 7, 8]) % 9]
+}
+
+const PESOS_SP: [u32; 8] = [1, 3, 4, 5, 6, 7, 8, 10];
+
+fn dv_sp(d: &[u32]) -> Vec<u32> {
+    let first_digit = soma(&d[..8], &PESOS_SP) % 11 % 10;
+    let mut corpo = d[..8].to_vec();
+    corpo.push(first_digit);
+    corpo.extend_from_slice(&d[9..11]);
+    let second_digit = soma(&corpo, &[3, 2, 10, 9, 8, 7, 6, 5, 4, 3, 2]) % 11 % 10;
+    vec![first_digit, second_digit]
+}
+
+fn dv_sp_produtor_rural(d: &[u32]) -> Vec<u32> {
+    vec![soma(&d[..8], &PESOS_SP) % 11 % 10]
+}
+
+/// TO no formato antigo (11 dígitos) ignora o código do tipo de empresa (3º e 4º dígitos).
+fn dv_to_onze_digitos(d: &[u32]) -> Vec<u32> {
+    let mut corpo = d[..2].to_vec();
+    corpo.extend_from_slice(&d[4..10]);
+    dv_nove_digitos(&corpo)
+}
+
+const fn regra(
+    tamanho: usize,
+    posicoes_dv: &'static [usize],
+    prefixos: &'static [&'static str],
+    mascara: &'static str,
+    calcular: fn(&[u32]) -> Vec<u32>,
+) -> Regra {
+    Regra {
+        tamanho,
+        posicoes_dv,
+        prefixos,
+        mascara,
+        calcular,
+        produtor_rural: false,
+    }
+}
+
+const DV_FINAL_9: &[usize] = &[8];
+
+const AC: &[Regra] = &[regra(13, &[11, 12], &["01"], "##.###.###/###-##", dv_ac_df)];
+const AL: &[Regra] = &[regra(
+    9,
+    DV_FINAL_9,
+    &["240", "243", "245", "247", "248"],
+    "#########",
+    dv_nove_digitos,
+)];
+const AP: &[Regra] = &[regra(9, DV_FINAL_9, &["03"], "#########", dv_ap)];
+const AM: &[Regra] = &[regra(9, DV_FINAL_9, &[], "##.###.###-#", dv_nove_digitos)];
+const BA: &[Regra] = &[
+    regra(9, &[7, 8], &[], "#######-##", dv_ba),
+    regra(8, &[6, 7], &[], "######-##", dv_ba),
+];
+const CE: &[Regra] = &[regra(9, DV_FINAL_9, &[], "########-#", dv_nove_digitos)];
+const DF: &[Regra] = &[regra(13, &[11, 12], &["07"], "##.######.###-##", dv_ac_df)];
+const ES: &[Regra] = &[regra(9, DV_FINAL_9, &[], "###.###.##-#", dv_nove_digitos)];
+const GO: &[Regra] = &[regra(
+    9,
+    DV_FINAL_9,
+    &[
+        "10", "11", "15", "20", "21", "22", "23", "24", "25", "26", "27", "28", "29",
+    ],
+    "##.###.###-#",
+    dv_go,
+)];
+const MA: &[Regra] = &[regra(9, DV_FINAL_9, &["12"], "#########", dv_nove_digitos)];
+const MT: &[Regra] = &[regra(11, &[10], &[], "##########-#", dv_mt)];
+const MS: &[Regra] = &[regra(
+    9,
+    DV_FINAL_9,
+    &["28", "50"],
+    "#########",
+    dv_nove_digitos,
+)];
+const MG: &[Regra] = &[regra(13, &[11, 12], &[], "###.###.###/####", dv_mg)];
+const PA: &[Regra] = &[regra(
+    9,
+    DV_FINAL_9,
+    &["15"],
+    "##-######-#",
+    dv_nove_digitos,
+)];
+const PB: &[Regra] = &[regra(9, DV_FINAL_9, &[], "########-#", dv_nove_digitos)];
+const PR: &[Regra] = &[regra(10, &[8, 9], &[], "########-##", dv_pr)];
+const PE: &[Regra] = &[regra(9, &[7, 8], &[], "#######-##", dv_pe)];
+const PI: &[Regra] = &[regra(9, DV_FINAL_9, &[], "#########", dv_nove_digitos)];
+const RJ: &[Regra] = &[regra(8, &[7], &[], "##.###.##-#", dv_rj)];
+const RN: &[Regra] = &[
+    regra(9, DV_FINAL_9, &["20"], "##.###.###-#", dv_nove_digitos),
+    regra(10, &[9], &["20"], "##.#.###.###-#", dv_rn_dez_digitos),
+];
+const RS: &[Regra] = &[regra(10, &[9], &[], "###/#######", dv_rs)];
+const RO: &[Regra] = &[regra(14, &[13], &[], "#############-#", dv_ro)];
+const RR: &[Regra] = &[regra(9, DV_FINAL_9, &["24"], "########-#", dv_rr)];
+const SC: &[Regra] = &[regra(9, DV_FINAL_9, &[], "###.###.###", dv_nove_digitos)];
+const SP: &[Regra] = &[
+    regra(12, &[8, 11], &[], "###.###.###.###", dv_sp),
+    Regra {
+        produtor_rural: true,
+        ..regra(12, &[8], &[], "P-########.#/###", dv_sp_produtor_rural)
+    },
+];
+const SE: &[Regra] = &[regra(9, DV_FINAL_9, &[], "########-#", dv_nove_digitos)];
+const TO: &[Regra] = &[
+    regra(9, DV_FINAL_9, &[], "#########", dv_nove_digitos),
+    regra(
+        11,
+        &[10],
+        &["##01", "##02", "##03", "##99"],
+        "###########",
+        dv_to_onze_digitos,
+    ),
+];
+
+fn regras_da_uf(uf: &str) -> Result<&'static [Regra], String> {
+    let regras = match uf.trim().to_ascii_uppercase().as_str() {
+        "AC" => AC,
+        "AL" => AL,
+        "AP" => AP,
+        "AM" => AM,
+        "BA" => BA,
+        "CE" => CE,
+        "DF" => DF,
+        "ES" => ES,
+        "GO" => GO,
+        "MA" => MA,
+        "MT" => MT,
+        "MS" => MS,
+        "MG" => MG,
+        "PA" => PA,
+        "PB" => PB,
+        "PR" => PR,
+        "PE" => PE,
+        "PI" => PI,
+        "RJ" => RJ,
+        "RN" => RN,
+        "RS" => RS,
+        "RO" => RO,
+        "RR" => RR,
+        "SC" => SC,
+        "SP" => SP,
+        "SE" => SE,
+        "TO" => TO,
+        uf => return Err(format!("UF desconhecida: {}", uf)),
+    };
+    Ok(regras)
+}
+
+fn aplicar_mascara(mascara: &str, digits: &[u32]) -> String {
+    let mut digits = digits.iter();
+    mascara
+        .chars()
+        .map(|c| match c {
+            '#' => digits
+                .next()
+                .and_then(|&d| char::from_digit(d, 10))
+                .unwrap_or('0'),
+            c => c,
+        })
+        .collect()
+}
+
+fn prefixo_valido(prefixos: &[&str], digits: &[u32]) -> bool {
+    prefixos.is_empty()
+        || prefixos.iter().any(|prefixo| {
+            prefixo
+                .chars()
+                .zip(digits.iter())
+                .all(|(c, &d)| c == '#' || c.to_digit(10) == Some(d))
+        })
+}
+
+fn para_string(digits: &[u32]) -> String {
+    digits.iter().map(|d| d.to_string()).collect()
+}
+
+/// Gera uma inscrição estadual válida para a UF. Em SP, `produtor_rural` gera o formato
+/// `P-` de produtor rural.
+pub fn gerar_inscricao_estadual(
+    uf: &str,
+    com_mascara: bool,
+    produtor_rural: Option<bool>,
+    semente: Option<u64>,
+) -> Result<String, String> {
+    let produtor_rural = produtor_rural.unwrap_or(false);
+    let regra = regras_da_uf(uf)?
+        .iter()
+        .find(|regra| regra.produtor_rural == produtor_rural)
+        .ok_or_else(|| format!("{} não possui inscrição de produtor rural", uf))?;
+
+    let mut gerador = GeradorDocumentos::new(semente);
+    let rng = gerador.rng();
+
+    let digits = loop {
+        let mut digits: Vec<u32> = (0..regra.tamanho)
+            .map(|_| rng.random_range(0..10))
+            .collect();
+
+        if !regra.prefixos.is_empty() {
+            let prefixo = regra.prefixos[rng.random_range(0..regra.prefixos.len())];
+            for (posicao, c) in prefixo.chars().enumerate() {
+                if let Some(d) = c.to_digit(10) {
+                    digits[posicao] = d;
+                }
+            }
+        }
+
+        for (&posicao, dv) in regra.posicoes_dv.iter().zip((regra.calcular)(&digits)) {
+            digits[posicao] = dv;
+        }
+
+        if !digits.windows(2).all(|w| w[0] == w[1]) {
+            break digits;
+        }
+    };
+
+    Ok(if com_mascara {
+        aplicar_mascara(regra.mascara, &digits)
+    } else if regra.produtor_rural {
+        format!("P{}", para_string(&digits))
+    } else {
+        para_string(&digits)
+    })
+}
+
+/// Valida a inscrição estadual segundo as regras da UF. A máscara é opcional e não é
+/// conferida, já que cada sistema formata a IE de um jeito.
+pub fn validar_inscricao_estadual(uf: &str, ie_input: &str) -> Result<ValidationReport, String> {
+    let regras = regras_da_uf(uf)?;
+
+    let normalized: String = ie_input
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut report = ValidationReport::new(ie_input, normalized.clone());
+
+    let (produtor_rural, numeros) = match normalized.strip_prefix('P') {
+        Some(numeros) if regras.iter().any(|regra| regra.produtor_rural) => (true, numeros),
+        _ => (false, normalized.as_str()),
+    };
+
+    if !numeros.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(report.fail(ValidationFailure::InvalidCharacters));
+    }
+
+    let digits: Vec<u32> = numeros.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    let Some(regra) = regras
+        .iter()
+        .find(|regra| regra.produtor_rural == produtor_rural && regra.tamanho == digits.len())
+    else {
+        return Ok(report.fail(ValidationFailure::WrongLength));
+    };
+
+    report.masked = Some(aplicar_mascara(regra.mascara, &digits));
+
+    if !prefixo_valido(regra.prefixos, &digits) {
+        return Ok(report.fail(ValidationFailure::InvalidPrefix));
+    }
+
+    if digits.windows(2).all(|w| w[0] == w[1]) {
+        return Ok(report.fail(ValidationFailure::RepeatedDigits));
+    }
+
+    let esperado = (regra.calcular)(&digits);
+    let informado: Vec<u32> = regra.posicoes_dv.iter().map(|&p| digits[p]).collect();
+    report.expected_check_digits = Some(para_string(&esperado));
+    report.supplied_check_digits = Some(para_string(&informado));
+
+    for (i, (e, s)) in esperado.iter().zip(informado.iter()).enumerate() {
+        if e != s {
+            return Ok(report.fail(if i == 0 {
+                ValidationFailure::FirstCheckDigitMismatch
+            } else {
+                ValidationFailure::SecondCheckDigitMismatch
+            }));
+        }
+    }
+
+    Ok(report.pass())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UFS: [&str; 27] = [
+        "AC", "AL", "AP", "AM", "BA", "CE", "DF", "ES", "GO", "MA", "MT", "MS", "MG", "PA", "PB",
+        "PR", "PE", "PI", "RJ", "RN", "RS", "RO", "RR", "SC", "SP", "SE", "TO",
+    ];
+
+    fn validar(uf: &str, ie: &str) -> ValidationReport {
+        validar_inscricao_estadual(uf, ie).unwrap()
+    }
+
+    #[test]
+    fn aceita_inscricoes_validas_conhecidas() {
+        let casos = [
+            ("AC", "01.004.823/001-12"),
+            ("BA", "123456-63"),
+            ("BA", "1000003-06"),
+            ("DF", "07.300001.001-09"),
+            ("GO", "10.987.654-7"),
+            ("GO", "15.123.456-6"),
+            ("MG", "062.307.904/0081"),
+            ("PE", "0321418-40"),
+            ("PR", "123.45678-50"),
+            ("RJ", "99.999.99-3"),
+            ("RO", "0000000062521-3"),
+            ("RR", "24006628-1"),
+            ("RS", "224/3658792"),
+            ("SP", "110.042.490.114"),
+            ("SP", "P-01100424.3/002"),
+            ("TO", "29010227836"),
+        ];
+        for (uf, ie) in casos {
+            let report = validar(uf, ie);
+            assert!(report.valid, "{} {}: {:?}", uf, ie, report.failure);
+        }
+    }
+
+    #[test]
+    fn reprova_cada_digito_verificador_errado() {
+        let casos = [
+            (
+                "GO",
+                "10.987.654-8",
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                "SP",
+                "110.042.491.114",
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                "SP",
+                "110.042.490.115",
+                ValidationFailure::SecondCheckDigitMismatch,
+            ),
+            (
+                "MG",
+                "062.307.904/0091",
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                "MG",
+                "062.307.904/0082",
+                ValidationFailure::SecondCheckDigitMismatch,
+            ),
+            (
+                "PR",
+                "123.45678-60",
+                ValidationFailure::FirstCheckDigitMismatch,
+            ),
+            (
+                "PR",
+                "123.45678-51",
+                ValidationFailure::SecondCheckDigitMismatch,
+            ),
+        ];
+        for (uf, ie, falha) in casos {
+            assert_eq!(validar(uf, ie).failure, Some(falha), "{} {}", uf, ie);
+        }
+    }
+
+    #[test]
+    fn reprova_prefixo_tamanho_e_uf_desconhecida() {
+        assert_eq!(
+            validar("GO", "12.345.678-0").failure,
+            Some(ValidationFailure::InvalidPrefix)
+        );
+        assert_eq!(
+            validar("SP", "110.042.490").failure,
+            Some(ValidationFailure::WrongLength)
+        );
+        assert_eq!(
+            validar("RJ", "99.999.9A-3").failure,
+            Some(ValidationFailure::InvalidCharacters)
+        );
+        assert!(validar_inscricao_estadual("XX", "123").is_err());
+        assert!(gerar_inscricao_estadual("RJ", false, Some(true), None).is_err());
+    }
+
+    #[test]
+    fn inscricoes_geradas_passam_na_validacao() {
+        for uf in UFS {
+            for semente in 0..30 {
+                for com_mascara in [false, true] {
+                    let ie =
+                        gerar_inscricao_estadual(uf, com_mascara, None, Some(semente)).unwrap();
+                    assert!(validar(uf, &ie).valid, "{} {}", uf, ie);
+                }
+            }
+        }
+        for semente in 0..30 {
+            let ie = gerar_inscricao_estadual("SP", false, Some(true), Some(semente)).unwrap();
+            assert!(ie.starts_with('P'));
+            assert!(validar("SP", &ie).valid, "{}", ie);
+        }
+    }
+}
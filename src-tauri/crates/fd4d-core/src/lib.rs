@@ -0,0 +1,20 @@
+//! Lógica do FD4D sem dependência do Tauri: geração e validação de documentos brasileiros,
+//! conversão de cores e um servidor de captura de webhooks embutível.
+
+pub mod anonimizacao;
+pub mod boleto;
+pub mod chave_acesso;
+pub mod color;
+pub mod cpf_cnpj;
+pub mod documentos;
+pub mod extracao;
+pub mod inscricao_estadual;
+pub mod lote;
+pub mod perfis;
+pub mod pix;
+pub mod placa;
+pub mod validacao;
+pub mod webhook;
+pub mod webhook_faults;
+pub mod webhook_rules;
+pub mod webhook_store;
@@ -0,0 +1,329 @@
+use crate::cpf_cnpj::{regiao_fiscal_da_uf, GeradorDocumentos, TipoDocumento};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+
+const MAX_QUANTIDADE: usize = 1_000_000;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatoExportacao {
+    Csv,
+    Json,
+    Ndjson,
+    Sql,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcoesLote {
+    pub tipo: TipoDocumento,
+    pub quantidade: usize,
+    pub formato: FormatoExportacao,
+    pub caminho: String,
+    #[serde(default)]
+    pub com_mascara: bool,
+    #[serde(default)]
+    pub sem_duplicatas: bool,
+    #[serde(default)]
+    pub alfanumerico: bool,
+    pub uf: Option<String>,
+    pub semente: Option<u64>,
+    pub tabela: Option<String>,
+    pub coluna: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultadoLote {
+    pub caminho: String,
+    pub quantidade: usize,
+    pub formato: FormatoExportacao,
+}
+
+impl ResultadoLote {
+    pub fn new(caminho: String, quantidade: usize, formato: FormatoExportacao) -> Self {
+        ResultadoLote {
+            caminho,
+            quantidade,
+            formato,
+        }
+    }
+}
+
+/// Aceita apenas identificadores SQL simples (letras, dígitos, `_` e `.` para schema).
+pub fn validar_identificador(nome: &str) -> Result<(), String> {
+    let valido = !nome.is_empty()
+        && !nome.starts_with(|c: char| c.is_ascii_digit())
+        && nome
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+
+    if valido {
+        Ok(())
+    } else {
+        Err(format!("Identificador inválido: {}", nome))
+    }
+}
+
+/// Opções já conferidas; o arquivo de destino só é criado depois de tudo validado.
+struct Destino {
+    coluna: String,
+    tabela: String,
+    regiao: Option<u32>,
+}
+
+fn preparar_lote(opcoes: &OpcoesLote) -> Result<Destino, String> {
+    if opcoes.quantidade == 0 || opcoes.quantidade > MAX_QUANTIDADE {
+        return Err(format!(
+            "Quantidade deve estar entre 1 e {}",
+            MAX_QUANTIDADE
+        ));
+    }
+
+    let regiao = match (opcoes.tipo, opcoes.uf.as_deref()) {
+        (_, None) => None,
+        (TipoDocumento::Cpf, Some(uf)) => Some(regiao_fiscal_da_uf(uf)?),
+        (TipoDocumento::Cnpj, Some(_)) => {
+            return Err("UF só pode ser informada na geração de CPF".to_string())
+        }
+    };
+
+    let coluna = opcoes.coluna.clone().unwrap_or_else(|| match opcoes.tipo {
+        TipoDocumento::Cpf => "cpf".to_string(),
+        TipoDocumento::Cnpj => "cnpj".to_string(),
+    });
+    validar_identificador(&coluna)?;
+
+    let tabela = opcoes
+        .tabela
+        .clone()
+        .unwrap_or_else(|| "documentos".to_string());
+    if let FormatoExportacao::Sql = opcoes.formato {
+        validar_identificador(&tabela)?;
+    }
+
+    Ok(Destino {
+        coluna,
+        tabela,
+        regiao,
+    })
+}
+
+fn escrever_lote(opcoes: &OpcoesLote, destino: &Destino, file: File) -> Result<usize, String> {
+    let Destino {
+        coluna,
+        tabela,
+        regiao,
+    } = destino;
+    let mut writer = BufWriter::new(file);
+    let io_err = |e: std::io::Error| format!("Falha ao escrever {}: {}", opcoes.caminho, e);
+
+    match opcoes.formato {
+        FormatoExportacao::Csv => writeln!(writer, "{}", coluna).map_err(io_err)?,
+        FormatoExportacao::Json => writeln!(writer, "[").map_err(io_err)?,
+        FormatoExportacao::Ndjson | FormatoExportacao::Sql => {}
+    }
+
+    let mut gerador = GeradorDocumentos::new(opcoes.semente);
+    let mut vistos: HashSet<String> = HashSet::new();
+    let mut gerados = 0;
+
+    while gerados < opcoes.quantidade {
+        let documento = match opcoes.tipo {
+            TipoDocumento::Cpf => gerador.cpf(opcoes.com_mascara, *regiao),
+            TipoDocumento::Cnpj => gerador.cnpj(opcoes.com_mascara, opcoes.alfanumerico),
+        };
+
+        if opcoes.sem_duplicatas && !vistos.insert(documento.clone()) {
+            continue;
+        }
+
+        match opcoes.formato {
+            FormatoExportacao::Csv => writeln!(writer, "{}", documento),
+            FormatoExportacao::Json => {
+                let separador = if gerados + 1 < opcoes.quantidade {
+                    ","
+                } else {
+                    ""
+                };
+                writeln!(
+                    writer,
+                    "  {}{}",
+                    serde_json::json!({ coluna.as_str(): documento }),
+                    separador
+                )
+            }
+            FormatoExportacao::Ndjson => {
+                writeln!(
+                    writer,
+                    "{}",
+                    serde_json::json!({ coluna.as_str(): documento })
+                )
+            }
+            FormatoExportacao::Sql => writeln!(
+                writer,
+                "INSERT INTO {} ({}) VALUES ('{}');",
+                tabela, coluna, documento
+            ),
+        }
+        .map_err(io_err)?;
+
+        gerados += 1;
+    }
+
+    if let FormatoExportacao::Json = opcoes.formato {
+        writeln!(writer, "]").map_err(io_err)?;
+    }
+
+    writer.flush().map_err(io_err)?;
+
+    Ok(gerados)
+}
+
+/// Gera `opcoes.quantidade` documentos direto no arquivo de destino, sem acumular em memória.
+/// Opções inválidas não tocam no arquivo; uma falha no meio da escrita apaga o arquivo parcial.
+pub fn gerar_documentos_em_lote(opcoes: &OpcoesLote) -> Result<ResultadoLote, String> {
+    let destino = preparar_lote(opcoes)?;
+
+    let file = File::create(&opcoes.caminho)
+        .map_err(|e| format!("Falha ao criar {}: {}", opcoes.caminho, e))?;
+    let quantidade = escrever_lote(opcoes, &destino, file).inspect_err(|_| {
+        let _ = fs::remove_file(&opcoes.caminho);
+    })?;
+
+    Ok(ResultadoLote::new(
+        opcoes.caminho.clone(),
+        quantidade,
+        opcoes.formato,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpf_cnpj::{validar_cnpj, validar_cpf};
+
+    fn opcoes(tipo: TipoDocumento, formato: FormatoExportacao, quantidade: usize) -> OpcoesLote {
+        let caminho = std::env::temp_dir().join(format!("fd4d-lote-{}", uuid::Uuid::new_v4()));
+        OpcoesLote {
+            tipo,
+            quantidade,
+            formato,
+            caminho: caminho.to_string_lossy().into_owned(),
+            com_mascara: false,
+            sem_duplicatas: true,
+            alfanumerico: false,
+            uf: None,
+            semente: Some(3),
+            tabela: None,
+            coluna: None,
+        }
+    }
+
+    fn gerar_e_ler(opcoes: &OpcoesLote) -> String {
+        let resultado = gerar_documentos_em_lote(opcoes).unwrap();
+        assert_eq!(resultado.quantidade, opcoes.quantidade);
+        let conteudo = fs::read_to_string(&opcoes.caminho).unwrap();
+        fs::remove_file(&opcoes.caminho).unwrap();
+        conteudo
+    }
+
+    #[test]
+    fn csv_tem_cabecalho_e_documentos_validos_sem_repeticao() {
+        let conteudo = gerar_e_ler(&opcoes(TipoDocumento::Cpf, FormatoExportacao::Csv, 500));
+        let mut linhas = conteudo.lines();
+        assert_eq!(linhas.next(), Some("cpf"));
+        let cpfs: HashSet<&str> = linhas.collect();
+        assert_eq!(cpfs.len(), 500);
+        assert!(cpfs.iter().all(|cpf| validar_cpf(cpf).valid));
+    }
+
+    #[test]
+    fn json_e_ndjson_sao_parseaveis() {
+        let conteudo = gerar_e_ler(&opcoes(TipoDocumento::Cnpj, FormatoExportacao::Json, 10));
+        let itens: Vec<serde_json::Value> = serde_json::from_str(&conteudo).unwrap();
+        assert_eq!(itens.len(), 10);
+        assert!(itens
+            .iter()
+            .all(|item| validar_cnpj(item["cnpj"].as_str().unwrap()).valid));
+
+        let conteudo = gerar_e_ler(&opcoes(TipoDocumento::Cnpj, FormatoExportacao::Ndjson, 10));
+        for linha in conteudo.lines() {
+            let item: serde_json::Value = serde_json::from_str(linha).unwrap();
+            assert!(validar_cnpj(item["cnpj"].as_str().unwrap()).valid);
+        }
+    }
+
+    #[test]
+    fn sql_usa_tabela_e_coluna_informadas() {
+        let mut opcoes = opcoes(TipoDocumento::Cpf, FormatoExportacao::Sql, 3);
+        opcoes.tabela = Some("public.clientes".to_string());
+        opcoes.coluna = Some("documento".to_string());
+        let conteudo = gerar_e_ler(&opcoes);
+        assert_eq!(conteudo.lines().count(), 3);
+        assert!(conteudo
+            .lines()
+            .all(|linha| linha.starts_with("INSERT INTO public.clientes (documento) VALUES ('")));
+    }
+
+    #[test]
+    fn rejeita_identificadores_e_quantidades_invalidos() {
+        assert!(validar_identificador("clientes; DROP TABLE x").is_err());
+        assert!(validar_identificador("1tabela").is_err());
+        assert!(validar_identificador("").is_err());
+        assert!(validar_identificador("schema.tabela_1").is_ok());
+
+        let mut opcoes = opcoes(TipoDocumento::Cpf, FormatoExportacao::Csv, 0);
+        assert!(gerar_documentos_em_lote(&opcoes).is_err());
+        opcoes.quantidade = MAX_QUANTIDADE + 1;
+        assert!(gerar_documentos_em_lote(&opcoes).is_err());
+    }
+
+    #[test]
+    fn opcoes_invalidas_nao_tocam_no_arquivo() {
+        let base = opcoes(TipoDocumento::Cpf, FormatoExportacao::Sql, 5);
+        fs::write(&base.caminho, "exportação anterior").unwrap();
+
+        let invalidas = [
+            OpcoesLote {
+                uf: Some("XX".to_string()),
+                ..base.clone()
+            },
+            OpcoesLote {
+                tipo: TipoDocumento::Cnpj,
+                uf: Some("SP".to_string()),
+                ..base.clone()
+            },
+            OpcoesLote {
+                tabela: Some("clientes; DROP TABLE x".to_string()),
+                ..base.clone()
+            },
+            OpcoesLote {
+                coluna: Some("1cpf".to_string()),
+                ..base.clone()
+            },
+            OpcoesLote {
+                quantidade: 0,
+                ..base.clone()
+            },
+        ];
+        for opcoes in invalidas {
+            assert!(gerar_documentos_em_lote(&opcoes).is_err());
+            assert_eq!(
+                fs::read_to_string(&base.caminho).unwrap(),
+                "exportação anterior"
+            );
+        }
+        fs::remove_file(&base.caminho).unwrap();
+    }
+
+    #[test]
+    fn cpf_por_uf_sai_da_regiao_fiscal() {
+        let mut opcoes = opcoes(TipoDocumento::Cpf, FormatoExportacao::Csv, 20);
+        opcoes.uf = Some("RS".to_string());
+        let conteudo = gerar_e_ler(&opcoes);
+        assert!(conteudo.lines().skip(1).all(|cpf| &cpf[8..9] == "0"));
+    }
+}
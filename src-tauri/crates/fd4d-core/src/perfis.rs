@@ -0,0 +1,837 @@
+use crate::cpf_cnpj::{regiao_fiscal_da_uf, GeradorDocumentos};
+use crate::inscricao_estadual::gerar_inscricao_estadual;
+use crate::lote::{validar_identificador, FormatoExportacao, ResultadoLote};
+use chrono::{Datelike, NaiveDate};
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+const MAX_PERFIS: usize = 100_000;
+
+/// UF, capital, DDD da capital e faixa (5 primeiros dígitos) de CEP da capital.
+type DadosUf = (&'static str, &'static str, u32, (u32, u32));
+
+const UFS: [DadosUf; 27] = [
+    ("AC", "Rio Branco", 68, (69900, 69923)),
+    ("AL", "Maceió", 82, (57000, 57099)),
+    ("AP", "Macapá", 96, (68900, 68914)),
+    ("AM", "Manaus", 92, (69000, 69099)),
+    ("BA", "Salvador", 71, (40000, 42599)),
+    ("CE", "Fortaleza", 85, (60000, 61599)),
+    ("DF", "Brasília", 61, (70000, 72799)),
+    ("ES", "Vitória", 27, (29000, 29099)),
+    ("GO", "Goiânia", 62, (74000, 74899)),
+    ("MA", "São Luís", 98, (65000, 65109)),
+    ("MT", "Cuiabá", 65, (78000, 78109)),
+    ("MS", "Campo Grande", 67, (79000, 79129)),
+    ("MG", "Belo Horizonte", 31, (30000, 31999)),
+    ("PA", "Belém", 91, (66000, 66999)),
+    ("PB", "João Pessoa", 83, (58000, 58099)),
+    ("PR", "Curitiba", 41, (80000, 82999)),
+    ("PE", "Recife", 81, (50000, 52999)),
+    ("PI", "Teresina", 86, (64000, 64099)),
+    ("RJ", "Rio de Janeiro", 21, (20000, 23799)),
+    ("RN", "Natal", 84, (59000, 59139)),
+    ("RS", "Porto Alegre", 51, (90000, 91999)),
+    ("RO", "Porto Velho", 69, (76800, 76834)),
+    ("RR", "Boa Vista", 95, (69300, 69339)),
+    ("SC", "Florianópolis", 48, (88000, 88099)),
+    ("SP", "São Paulo", 11, (1000, 5999)),
+    ("SE", "Aracaju", 79, (49000, 49099)),
+    ("TO", "Palmas", 63, (77000, 77270)),
+];
+
+const NOMES_MASCULINOS: &[&str] = &[
+    "João",
+    "José",
+    "Antônio",
+    "Francisco",
+    "Carlos",
+    "Paulo",
+    "Pedro",
+    "Lucas",
+    "Luiz",
+    "Marcos",
+    "Luís",
+    "Gabriel",
+    "Rafael",
+    "Daniel",
+    "Marcelo",
+    "Bruno",
+    "Eduardo",
+    "Felipe",
+    "Rodrigo",
+    "Gustavo",
+    "Mateus",
+    "André",
+    "Fernando",
+    "Fábio",
+    "Leonardo",
+    "Thiago",
+    "Diego",
+    "Vinícius",
+    "Ricardo",
+    "Henrique",
+];
+
+const NOMES_FEMININOS: &[&str] = &[
+    "Maria",
+    "Ana",
+    "Francisca",
+    "Antônia",
+    "Adriana",
+    "Juliana",
+    "Márcia",
+    "Fernanda",
+    "Patrícia",
+    "Aline",
+    "Sandra",
+    "Camila",
+    "Amanda",
+    "Bruna",
+    "Jéssica",
+    "Letícia",
+    "Júlia",
+    "Luciana",
+    "Vanessa",
+    "Mariana",
+    "Gabriela",
+    "Beatriz",
+    "Larissa",
+    "Carolina",
+    "Renata",
+    "Débora",
+    "Cláudia",
+    "Helena",
+    "Isabela",
+    "Raquel",
+];
+
+const SOBRENOMES: &[&str] = &[
+    "Silva",
+    "Santos",
+    "Oliveira",
+    "Souza",
+    "Rodrigues",
+    "Ferreira",
+    "Alves",
+    "Pereira",
+    "Lima",
+    "Gomes",
+    "Costa",
+    "Ribeiro",
+    "Martins",
+    "Carvalho",
+    "Almeida",
+    "Lopes",
+    "Soares",
+    "Fernandes",
+    "Vieira",
+    "Barbosa",
+    "Rocha",
+    "Dias",
+    "Nascimento",
+    "Andrade",
+    "Moreira",
+    "Nunes",
+    "Marques",
+    "Machado",
+    "Mendes",
+    "Freitas",
+    "Cardoso",
+    "Ramos",
+    "Gonçalves",
+    "Santana",
+    "Teixeira",
+    "Araújo",
+    "Pinto",
+    "Moraes",
+    "Campos",
+    "Barros",
+];
+
+const TIPOS_LOGRADOURO: &[&str] = &["Rua", "Avenida", "Travessa", "Alameda", "Praça"];
+
+const NOMES_LOGRADOURO: &[&str] = &[
+    "das Flores",
+    "Sete de Setembro",
+    "XV de Novembro",
+    "Tiradentes",
+    "Dom Pedro II",
+    "Santos Dumont",
+    "das Palmeiras",
+    "São João",
+    "Rui Barbosa",
+    "Getúlio Vargas",
+    "Castro Alves",
+    "Marechal Deodoro",
+    "Floriano Peixoto",
+    "da Liberdade",
+    "Brasil",
+    "Treze de Maio",
+    "Duque de Caxias",
+    "Barão do Rio Branco",
+    "Independência",
+    "dos Andradas",
+];
+
+const BAIRROS: &[&str] = &[
+    "Centro",
+    "Jardim América",
+    "Vila Nova",
+    "Boa Vista",
+    "Santa Cruz",
+    "São José",
+    "Bela Vista",
+    "Jardim Paulista",
+    "Industrial",
+    "Alto da Glória",
+    "Cidade Nova",
+    "Vila Maria",
+    "Santo Antônio",
+    "Parque das Árvores",
+    "Jardim Europa",
+];
+
+const COMPLEMENTOS_PESSOA: &[&str] = &["Apto 12", "Apto 304", "Casa 2", "Bloco B", "Fundos"];
+const COMPLEMENTOS_EMPRESA: &[&str] = &["Sala 101", "Sala 1203", "Loja 4", "Galpão 2", "Andar 5"];
+
+/// Domínios reservados (RFC 2606): nenhum e-mail gerado chega a uma caixa real.
+const DOMINIOS_EMAIL: &[&str] = &["example.com", "example.org", "example.net"];
+
+/// Código, descrição, rótulo usado na razão social e rótulo curto do nome fantasia.
+const CNAES: &[(&str, &str, &str, &str)] = &[
+    (
+        "6201-5/01",
+        "Desenvolvimento de programas de computador sob encomenda",
+        "Tecnologia",
+        "Tech",
+    ),
+    (
+        "4711-3/02",
+        "Comércio varejista de mercadorias em geral, com predominância de produtos alimentícios - supermercados",
+        "Supermercados",
+        "Mercado",
+    ),
+    (
+        "5611-2/01",
+        "Restaurantes e similares",
+        "Restaurante",
+        "Sabores",
+    ),
+    (
+        "4781-4/00",
+        "Comércio varejista de artigos do vestuário e acessórios",
+        "Confecções",
+        "Moda",
+    ),
+    (
+        "8599-6/04",
+        "Treinamento em desenvolvimento profissional e gerencial",
+        "Treinamentos",
+        "Educa",
+    ),
+    (
+        "6911-7/01",
+        "Serviços advocatícios",
+        "Advogados Associados",
+        "Advocacia",
+    ),
+    (
+        "4120-4/00",
+        "Construção de edifícios",
+        "Construtora",
+        "Construções",
+    ),
+    (
+        "8630-5/03",
+        "Atividade médica ambulatorial restrita a consultas",
+        "Clínica Médica",
+        "Saúde",
+    ),
+    (
+        "4930-2/02",
+        "Transporte rodoviário de carga, exceto produtos perigosos e mudanças, intermunicipal, interestadual e internacional",
+        "Transportes",
+        "Log",
+    ),
+    (
+        "7319-0/02",
+        "Promoção de vendas",
+        "Marketing",
+        "Mkt",
+    ),
+    (
+        "4744-0/01",
+        "Comércio varejista de ferragens e ferramentas",
+        "Ferragens",
+        "Ferramentas",
+    ),
+    (
+        "9602-5/01",
+        "Cabeleireiros, manicure e pedicure",
+        "Salão de Beleza",
+        "Beleza",
+    ),
+    (
+        "4771-7/01",
+        "Comércio varejista de produtos farmacêuticos, sem manipulação de fórmulas",
+        "Farmácia",
+        "Farma",
+    ),
+    (
+        "6920-6/01",
+        "Atividades de contabilidade",
+        "Contabilidade",
+        "Contábil",
+    ),
+    (
+        "1091-1/02",
+        "Fabricação de produtos de padaria e confeitaria com predominância de produção própria",
+        "Panificadora",
+        "Pães",
+    ),
+];
+
+const ADJETIVOS_FANTASIA: &[&str] = &[
+    "Nova", "Prime", "Alfa", "Total", "Mega", "Real", "Brasil", "Master", "Express", "Max",
+    "Ideal", "Viva", "Central", "Premium", "Estrela",
+];
+
+const NATUREZAS: &[&str] = &["Ltda", "S.A.", "Ltda ME", "Ltda EPP"];
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum TipoPerfil {
+    Pessoa,
+    Empresa,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Endereco {
+    pub logradouro: String,
+    pub numero: String,
+    pub complemento: Option<String>,
+    pub bairro: String,
+    pub cidade: String,
+    pub uf: String,
+    pub cep: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Pessoa {
+    pub nome: String,
+    pub sexo: String,
+    /// Data no formato ISO (`AAAA-MM-DD`).
+    pub data_nascimento: String,
+    pub cpf: String,
+    pub rg: String,
+    pub orgao_emissor: String,
+    pub nome_mae: String,
+    pub telefone: String,
+    pub email: String,
+    pub endereco: Endereco,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Cnae {
+    pub codigo: String,
+    pub descricao: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Empresa {
+    pub razao_social: String,
+    pub nome_fantasia: String,
+    pub cnpj: String,
+    pub inscricao_estadual: String,
+    pub cnae: Cnae,
+    pub telefone: String,
+    pub email: String,
+    pub endereco: Endereco,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OpcoesPerfis {
+    pub tipo: TipoPerfil,
+    pub quantidade: usize,
+    pub formato: FormatoExportacao,
+    pub caminho: String,
+    #[serde(default)]
+    pub com_mascara: bool,
+    #[serde(default)]
+    pub alfanumerico: bool,
+    pub uf: Option<String>,
+    pub semente: Option<u64>,
+    pub tabela: Option<String>,
+}
+
+fn sem_acentos(texto: &str) -> String {
+    texto
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'ê' | 'è' | 'ë' => 'e',
+            'í' | 'î' | 'ì' | 'ï' => 'i',
+            'ó' | 'ô' | 'õ' | 'ò' | 'ö' => 'o',
+            'ú' | 'û' | 'ù' | 'ü' => 'u',
+            'ç' => 'c',
+            c => c,
+        })
+        .collect()
+}
+
+/// Versão minúscula, sem acentos e só com letras/dígitos, para e-mails.
+fn slug(texto: &str) -> String {
+    sem_acentos(&texto.to_lowercase())
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Dígito do RG no padrão da SSP-SP, usado para todas as UFs: pesos 2 a 9 e
+/// `11 - soma % 11`, em que 10 vira `X` e 11 vira `0`.
+fn digito_rg(digits: &[u32]) -> char {
+    let soma: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| d * (i as u32 + 2))
+        .sum();
+    match 11 - soma % 11 {
+        10 => 'X',
+        11 => '0',
+        digito => char::from_digit(digito, 10).unwrap(),
+    }
+}
+
+struct GeradorPerfis {
+    documentos: GeradorDocumentos,
+    com_mascara: bool,
+}
+
+impl GeradorPerfis {
+    fn new(com_mascara: bool, semente: Option<u64>) -> Self {
+        GeradorPerfis {
+            documentos: GeradorDocumentos::new(semente),
+            com_mascara,
+        }
+    }
+
+    fn escolher<'a>(&mut self, lista: &[&'a str]) -> &'a str {
+        lista.choose(self.documentos.rng()).unwrap()
+    }
+
+    /// Sorteia `quantidade` sobrenomes distintos.
+    fn sobrenomes(&mut self, quantidade: usize) -> Vec<&'static str> {
+        SOBRENOMES
+            .choose_multiple(self.documentos.rng(), quantidade)
+            .copied()
+            .collect()
+    }
+
+    fn digitos(&mut self, quantidade: usize) -> String {
+        let rng = self.documentos.rng();
+        (0..quantidade)
+            .map(|_| char::from_digit(rng.random_range(0..10), 10).unwrap())
+            .collect()
+    }
+
+    fn dados_uf(&mut self, uf: Option<&str>) -> Result<DadosUf, String> {
+        match uf {
+            Some(uf) => {
+                let uf = uf.trim().to_ascii_uppercase();
+                UFS.iter()
+                    .find(|(sigla, ..)| *sigla == uf)
+                    .copied()
+                    .ok_or_else(|| format!("UF desconhecida: {}", uf))
+            }
+            None => Ok(*UFS.choose(self.documentos.rng()).unwrap()),
+        }
+    }
+
+    fn telefone(&mut self, ddd: u32, celular: bool) -> String {
+        let (prefixo, tamanho) = if celular { ("9", 8) } else { ("3", 7) };
+        let numero = format!("{}{}", prefixo, self.digitos(tamanho));
+        if self.com_mascara {
+            let meio = numero.len() - 4;
+            format!("({}) {}-{}", ddd, &numero[..meio], &numero[meio..])
+        } else {
+            format!("{}{}", ddd, numero)
+        }
+    }
+
+    fn endereco(
+        &mut self,
+        (uf, capital, _, (cep_min, cep_max)): DadosUf,
+        complementos: &[&str],
+    ) -> Endereco {
+        let logradouro = format!(
+            "{} {}",
+            self.escolher(TIPOS_LOGRADOURO),
+            self.escolher(NOMES_LOGRADOURO)
+        );
+        let rng = self.documentos.rng();
+        let numero = rng.random_range(1..=3000).to_string();
+        let tem_complemento = rng.random_bool(0.4);
+        let cep_prefixo = rng.random_range(cep_min..=cep_max);
+        let complemento = tem_complemento.then(|| self.escolher(complementos).to_string());
+        let cep_sufixo = self.digitos(3);
+
+        Endereco {
+            logradouro,
+            numero,
+            complemento,
+            bairro: self.escolher(BAIRROS).to_string(),
+            cidade: capital.to_string(),
+            uf: uf.to_string(),
+            cep: if self.com_mascara {
+                format!("{:05}-{}", cep_prefixo, cep_sufixo)
+            } else {
+                format!("{:05}{}", cep_prefixo, cep_sufixo)
+            },
+        }
+    }
+
+    fn pessoa(&mut self, uf: Option<&str>) -> Result<Pessoa, String> {
+        let dados_uf = self.dados_uf(uf)?;
+        let (sigla, _, ddd, _) = dados_uf;
+
+        let feminino = self.documentos.rng().random_bool(0.5);
+        let primeiro_nome = self.escolher(if feminino {
+            NOMES_FEMININOS
+        } else {
+            NOMES_MASCULINOS
+        });
+        // Como de costume no Brasil, o último sobrenome da mãe vem antes do sobrenome do pai.
+        let &[sobrenome_avo, sobrenome_mae, sobrenome_pai] = self.sobrenomes(3).as_slice() else {
+            unreachable!()
+        };
+        let nome_mae = format!(
+            "{} {} {}",
+            self.escolher(NOMES_FEMININOS),
+            sobrenome_avo,
+            sobrenome_mae
+        );
+        let nome = format!("{} {} {}", primeiro_nome, sobrenome_mae, sobrenome_pai);
+
+        let inicio = NaiveDate::from_ymd_opt(1945, 1, 1)
+            .unwrap()
+            .num_days_from_ce();
+        let fim = NaiveDate::from_ymd_opt(2006, 12, 31)
+            .unwrap()
+            .num_days_from_ce();
+        let dias = self.documentos.rng().random_range(inicio..=fim);
+        let data_nascimento = NaiveDate::from_num_days_from_ce_opt(dias).unwrap();
+
+        let cpf = self
+            .documentos
+            .cpf(self.com_mascara, Some(regiao_fiscal_da_uf(sigla)?));
+
+        let rg_base = self.digitos(8);
+        let rg_digitos: Vec<u32> = rg_base.chars().filter_map(|c| c.to_digit(10)).collect();
+        let rg_dv = digito_rg(&rg_digitos);
+        let rg = if self.com_mascara {
+            format!(
+                "{}.{}.{}-{}",
+                &rg_base[..2],
+                &rg_base[2..5],
+                &rg_base[5..],
+                rg_dv
+            )
+        } else {
+            format!("{}{}", rg_base, rg_dv)
+        };
+
+        let email = format!(
+            "{}.{}{}@{}",
+            slug(primeiro_nome),
+            slug(sobrenome_pai),
+            self.documentos.rng().random_range(1..100),
+            self.escolher(DOMINIOS_EMAIL)
+        );
+
+        Ok(Pessoa {
+            nome,
+            sexo: if feminino { "F" } else { "M" }.to_string(),
+            data_nascimento: data_nascimento.format("%Y-%m-%d").to_string(),
+            cpf,
+            rg,
+            orgao_emissor: format!("SSP/{}", sigla),
+            nome_mae,
+            telefone: self.telefone(ddd, true),
+            email,
+            endereco: self.endereco(dados_uf, COMPLEMENTOS_PESSOA),
+        })
+    }
+
+    fn empresa(&mut self, uf: Option<&str>, alfanumerico: bool) -> Result<Empresa, String> {
+        let dados_uf = self.dados_uf(uf)?;
+        let (sigla, _, ddd, _) = dados_uf;
+
+        let &(codigo, descricao, rotulo, rotulo_curto) =
+            CNAES.choose(self.documentos.rng()).unwrap();
+        let socios = self.sobrenomes(2).join(" ");
+        let razao_social = format!("{} {} {}", rotulo, socios, self.escolher(NATUREZAS));
+        let nome_fantasia = format!("{} {}", self.escolher(ADJETIVOS_FANTASIA), rotulo_curto);
+
+        let cnpj = self.documentos.cnpj(self.com_mascara, alfanumerico);
+        let semente_ie = self.documentos.rng().random();
+        let inscricao_estadual =
+            gerar_inscricao_estadual(sigla, self.com_mascara, None, Some(semente_ie))?;
+
+        Ok(Empresa {
+            email: format!("contato@{}.example.com", slug(&nome_fantasia)),
+            razao_social,
+            nome_fantasia,
+            cnpj,
+            inscricao_estadual,
+            cnae: Cnae {
+                codigo: codigo.to_string(),
+                descricao: descricao.to_string(),
+            },
+            telefone: self.telefone(ddd, false),
+            endereco: self.endereco(dados_uf, COMPLEMENTOS_EMPRESA),
+        })
+    }
+}
+
+/// Colunas achatadas (`endereco_cep`, `cnae_codigo`...) para CSV e SQL.
+trait Colunas: Serialize {
+    fn colunas(&self) -> Vec<(&'static str, String)>;
+}
+
+fn colunas_endereco(endereco: &Endereco) -> Vec<(&'static str, String)> {
+    vec![
+        ("endereco_logradouro", endereco.logradouro.clone()),
+        ("endereco_numero", endereco.numero.clone()),
+        (
+            "endereco_complemento",
+            endereco.complemento.clone().unwrap_or_default(),
+        ),
+        ("endereco_bairro", endereco.bairro.clone()),
+        ("endereco_cidade", endereco.cidade.clone()),
+        ("endereco_uf", endereco.uf.clone()),
+        ("endereco_cep", endereco.cep.clone()),
+    ]
+}
+
+impl Colunas for Pessoa {
+    fn colunas(&self) -> Vec<(&'static str, String)> {
+        let mut colunas = vec![
+            ("nome", self.nome.clone()),
+            ("sexo", self.sexo.clone()),
+            ("data_nascimento", self.data_nascimento.clone()),
+            ("cpf", self.cpf.clone()),
+            ("rg", self.rg.clone()),
+            ("orgao_emissor", self.orgao_emissor.clone()),
+            ("nome_mae", self.nome_mae.clone()),
+            ("telefone", self.telefone.clone()),
+            ("email", self.email.clone()),
+        ];
+        colunas.extend(colunas_endereco(&self.endereco));
+        colunas
+    }
+}
+
+impl Colunas for Empresa {
+    fn colunas(&self) -> Vec<(&'static str, String)> {
+        let mut colunas = vec![
+            ("razao_social", self.razao_social.clone()),
+            ("nome_fantasia", self.nome_fantasia.clone()),
+            ("cnpj", self.cnpj.clone()),
+            ("inscricao_estadual", self.inscricao_estadual.clone()),
+            ("cnae_codigo", self.cnae.codigo.clone()),
+            ("cnae_descricao", self.cnae.descricao.clone()),
+            ("telefone", self.telefone.clone()),
+            ("email", self.email.clone()),
+        ];
+        colunas.extend(colunas_endereco(&self.endereco));
+        colunas
+    }
+}
+
+fn campo_csv(valor: &str) -> String {
+    if valor.contains([',', '"', '\n']) {
+        format!("\"{}\"", valor.replace('"', "\"\""))
+    } else {
+        valor.to_string()
+    }
+}
+
+fn escrever_perfis<T: Colunas>(
+    opcoes: &OpcoesPerfis,
+    mut gerar: impl FnMut() -> Result<T, String>,
+) -> Result<usize, String> {
+    let tabela = opcoes.tabela.clone().unwrap_or_else(|| match opcoes.tipo {
+        TipoPerfil::Pessoa => "pessoas".to_string(),
+        TipoPerfil::Empresa => "empresas".to_string(),
+    });
+    if let FormatoExportacao::Sql = opcoes.formato {
+        validar_identificador(&tabela)?;
+    }
+
+    let file = File::create(&opcoes.caminho)
+        .map_err(|e| format!("Falha ao criar {}: {}", opcoes.caminho, e))?;
+    let mut writer = BufWriter::new(file);
+    let io_err = |e: std::io::Error| format!("Falha ao escrever {}: {}", opcoes.caminho, e);
+    let json_err = |e: serde_json::Error| format!("Falha ao escrever {}: {}", opcoes.caminho, e);
+
+    if let FormatoExportacao::Json = opcoes.formato {
+        writeln!(writer, "[").map_err(io_err)?;
+    }
+
+    for i in 0..opcoes.quantidade {
+        let perfil = gerar()?;
+
+        match opcoes.formato {
+            FormatoExportacao::Csv => {
+                let colunas = perfil.colunas();
+                if i == 0 {
+                    let cabecalho: Vec<&str> = colunas.iter().map(|(nome, _)| *nome).collect();
+                    writeln!(writer, "{}", cabecalho.join(",")).map_err(io_err)?;
+                }
+                let valores: Vec<String> =
+                    colunas.iter().map(|(_, valor)| campo_csv(valor)).collect();
+                writeln!(writer, "{}", valores.join(",")).map_err(io_err)?;
+            }
+            FormatoExportacao::Json => {
+                let separador = if i + 1 < opcoes.quantidade { "," } else { "" };
+                let json = serde_json::to_string(&perfil).map_err(json_err)?;
+                writeln!(writer, "  {}{}", json, separador).map_err(io_err)?;
+            }
+            FormatoExportacao::Ndjson => {
+                let json = serde_json::to_string(&perfil).map_err(json_err)?;
+                writeln!(writer, "{}", json).map_err(io_err)?;
+            }
+            FormatoExportacao::Sql => {
+                let colunas = perfil.colunas();
+                let nomes: Vec<&str> = colunas.iter().map(|(nome, _)| *nome).collect();
+                let valores: Vec<String> = colunas
+                    .iter()
+                    .map(|(_, valor)| format!("'{}'", valor.replace('\'', "''")))
+                    .collect();
+                writeln!(
+                    writer,
+                    "INSERT INTO {} ({}) VALUES ({});",
+                    tabela,
+                    nomes.join(", "),
+                    valores.join(", ")
+                )
+                .map_err(io_err)?;
+            }
+        }
+    }
+
+    if let FormatoExportacao::Json = opcoes.formato {
+        writeln!(writer, "]").map_err(io_err)?;
+    }
+
+    writer.flush().map_err(io_err)?;
+    Ok(opcoes.quantidade)
+}
+
+/// Gera uma pessoa fictícia coerente: CPF da região fiscal, DDD e CEP da UF do endereço.
+pub fn gerar_pessoa(
+    uf: Option<&str>,
+    com_mascara: bool,
+    semente: Option<u64>,
+) -> Result<Pessoa, String> {
+    GeradorPerfis::new(com_mascara, semente).pessoa(uf)
+}
+
+/// Gera uma empresa fictícia com CNPJ, IE da UF do endereço e CNAE.
+pub fn gerar_empresa(
+    uf: Option<&str>,
+    com_mascara: bool,
+    alfanumerico: bool,
+    semente: Option<u64>,
+) -> Result<Empresa, String> {
+    GeradorPerfis::new(com_mascara, semente).empresa(uf, alfanumerico)
+}
+
+/// Exporta `quantidade` perfis para um arquivo. Em CSV e SQL o endereço e o CNAE
+/// viram colunas próprias (`endereco_cep`, `cnae_codigo`...).
+pub fn exportar_perfis(opcoes: &OpcoesPerfis) -> Result<ResultadoLote, String> {
+    if opcoes.quantidade == 0 || opcoes.quantidade > MAX_PERFIS {
+        return Err(format!("Quantidade deve estar entre 1 e {}", MAX_PERFIS));
+    }
+
+    let mut gerador = GeradorPerfis::new(opcoes.com_mascara, opcoes.semente);
+    let uf = opcoes.uf.as_deref();
+    let quantidade = match opcoes.tipo {
+        TipoPerfil::Pessoa => escrever_perfis(opcoes, || gerador.pessoa(uf))?,
+        TipoPerfil::Empresa => {
+            escrever_perfis(opcoes, || gerador.empresa(uf, opcoes.alfanumerico))?
+        }
+    };
+
+    Ok(ResultadoLote::new(
+        opcoes.caminho.clone(),
+        quantidade,
+        opcoes.formato,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cpf_cnpj::{validar_cnpj, validar_cpf};
+    use crate::inscricao_estadual::validar_inscricao_estadual;
+
+    fn digitos(texto: &str) -> Vec<u32> {
+        texto.chars().filter_map(|c| c.to_digit(10)).collect()
+    }
+
+    #[test]
+    fn digito_do_rg_segue_a_ssp_sp() {
+        assert_eq!(digito_rg(&digitos("24678131")), '2');
+        assert_eq!(digito_rg(&digitos("39107722")), '3');
+        assert_eq!(digito_rg(&digitos("24678135")), 'X');
+        assert_eq!(digito_rg(&digitos("24678130")), '0');
+    }
+
+    #[test]
+    fn pessoa_tem_documentos_coerentes_com_a_uf() {
+        for semente in 0..50 {
+            let pessoa = gerar_pessoa(Some("SP"), true, Some(semente)).unwrap();
+            let cpf = validar_cpf(&pessoa.cpf);
+            assert!(cpf.valid, "{}", pessoa.cpf);
+            assert_eq!(cpf.likely_ufs, ["SP"]);
+            assert_eq!(pessoa.endereco.uf, "SP");
+            assert_eq!(pessoa.orgao_emissor, "SSP/SP");
+
+            let rg = digitos(&pessoa.rg);
+            let dv = pessoa.rg.chars().last().unwrap();
+            assert_eq!(pessoa.rg.len(), 12, "{}", pessoa.rg);
+            assert_eq!(digito_rg(&rg[..8]), dv, "{}", pessoa.rg);
+        }
+        assert!(gerar_pessoa(Some("XX"), false, None).is_err());
+    }
+
+    #[test]
+    fn empresa_tem_cnpj_e_ie_validos() {
+        for semente in 0..50 {
+            let empresa = gerar_empresa(Some("MG"), false, true, Some(semente)).unwrap();
+            assert!(validar_cnpj(&empresa.cnpj).valid, "{}", empresa.cnpj);
+            let ie = validar_inscricao_estadual("MG", &empresa.inscricao_estadual).unwrap();
+            assert!(ie.valid, "{}", empresa.inscricao_estadual);
+        }
+    }
+
+    #[test]
+    fn mesma_semente_gera_o_mesmo_perfil() {
+        let a = gerar_pessoa(None, false, Some(8)).unwrap();
+        let b = gerar_pessoa(None, false, Some(8)).unwrap();
+        assert_eq!(
+            serde_json::to_string(&a).unwrap(),
+            serde_json::to_string(&b).unwrap()
+        );
+    }
+}
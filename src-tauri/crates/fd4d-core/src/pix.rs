@@ -0,0 +1,637 @@
+use crate::cpf_cnpj::{validar_cnpj, validar_cpf};
+use serde::{Deserialize, Serialize};
+
+/// Identificador do arranjo PIX dentro do campo 26 (Merchant Account Information).
+const GUI_PIX: &str = "br.gov.bcb.pix";
+const MAX_EMAIL: usize = 77;
+const MAX_NOME: usize = 25;
+const MAX_CIDADE: usize = 15;
+const MAX_TXID: usize = 25;
+const MAX_VALOR: usize = 13;
+/// Tamanho máximo do valor de um campo TLV (dois dígitos no cabeçalho).
+const MAX_CAMPO: usize = 99;
+/// Sem txid, o BR Code estático usa `***`.
+const TXID_AUSENTE: &str = "***";
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TipoChavePix {
+    Cpf,
+    Cnpj,
+    Telefone,
+    Email,
+    /// Chave aleatória (EVP): UUID em minúsculas.
+    Aleatoria,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidacaoChavePix {
+    pub valida: bool,
+    pub tipo: Option<TipoChavePix>,
+    /// Chave no formato registrado no DICT: só dígitos, `+55...`, e-mail em minúsculas.
+    pub normalizada: Option<String>,
+    pub erro: Option<String>,
+}
+
+/// Dados de um BR Code estático. Também é o que `ler_br_code` extrai de um copia e cola.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DadosBrCode {
+    pub chave: String,
+    pub nome: String,
+    pub cidade: String,
+    pub valor_centavos: Option<u64>,
+    pub txid: Option<String>,
+    pub descricao: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CampoBrCode {
+    pub id: String,
+    pub nome: String,
+    pub valor: String,
+    pub subcampos: Vec<CampoBrCode>,
+    pub erro: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LeituraBrCode {
+    pub valido: bool,
+    pub campos: Vec<CampoBrCode>,
+    /// Problemas que não pertencem a um campo só: estrutura, campos ausentes, ordem.
+    pub erros: Vec<String>,
+    pub crc_esperado: Option<String>,
+    pub crc_informado: Option<String>,
+    pub dados: Option<DadosBrCode>,
+}
+
+fn chave_invalida(tipo: Option<TipoChavePix>, erro: &str) -> ValidacaoChavePix {
+    ValidacaoChavePix {
+        valida: false,
+        tipo,
+        normalizada: None,
+        erro: Some(erro.to_string()),
+    }
+}
+
+fn chave_valida(tipo: TipoChavePix, normalizada: String) -> ValidacaoChavePix {
+    ValidacaoChavePix {
+        valida: true,
+        tipo: Some(tipo),
+        normalizada: Some(normalizada),
+        erro: None,
+    }
+}
+
+fn eh_uuid(valor: &str) -> bool {
+    valor.len() == 36
+        && valor.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Sem `tipo`, deduz pelo formato: `@` é e-mail, `+` é telefone, UUID é chave aleatória e
+/// 11 ou 14 caracteres são CPF ou CNPJ.
+fn detectar_tipo(chave: &str) -> Option<TipoChavePix> {
+    if chave.contains('@') {
+        return Some(TipoChavePix::Email);
+    }
+    if chave.starts_with('+') {
+        return Some(TipoChavePix::Telefone);
+    }
+    if eh_uuid(chave) {
+        return Some(TipoChavePix::Aleatoria);
+    }
+    match chave.chars().filter(|c| c.is_ascii_alphanumeric()).count() {
+        11 => Some(TipoChavePix::Cpf),
+        14 => Some(TipoChavePix::Cnpj),
+        _ => None,
+    }
+}
+
+fn validar_telefone(chave: &str) -> ValidacaoChavePix {
+    let tipo = Some(TipoChavePix::Telefone);
+    let normalizada: String = chave
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '(' | ')' | '-'))
+        .collect();
+
+    let Some(numero) = normalizada.strip_prefix('+') else {
+        return chave_invalida(
+            tipo,
+            "Telefone deve estar no formato internacional (+55...)",
+        );
+    };
+    if numero.is_empty() || !numero.chars().all(|c| c.is_ascii_digit()) {
+        return chave_invalida(tipo, "Telefone deve conter apenas dígitos após o +");
+    }
+    if numero.starts_with('0') || numero.len() > 15 {
+        return chave_invalida(tipo, "Telefone fora do padrão E.164");
+    }
+    if let Some(nacional) = numero.strip_prefix("55") {
+        if !matches!(nacional.len(), 10 | 11) || nacional.starts_with('0') {
+            return chave_invalida(tipo, "Telefone brasileiro deve ter DDD e 8 ou 9 dígitos");
+        }
+    }
+
+    chave_valida(TipoChavePix::Telefone, normalizada)
+}
+
+fn validar_email(chave: &str) -> ValidacaoChavePix {
+    let tipo = Some(TipoChavePix::Email);
+    let normalizada = chave.trim().to_lowercase();
+
+    if normalizada.chars().count() > MAX_EMAIL {
+        return chave_invalida(tipo, "E-mail deve ter no máximo 77 caracteres");
+    }
+    let Some((local, dominio)) = normalizada.split_once('@') else {
+        return chave_invalida(tipo, "E-mail inválido");
+    };
+    let dominio_valido = dominio.contains('.')
+        && !dominio.starts_with('.')
+        && !dominio.ends_with('.')
+        && !dominio.contains("..");
+    if local.is_empty()
+        || !dominio_valido
+        || dominio.contains('@')
+        || normalizada.chars().any(char::is_whitespace)
+    {
+        return chave_invalida(tipo, "E-mail inválido");
+    }
+
+    chave_valida(TipoChavePix::Email, normalizada)
+}
+
+/// Valida uma chave PIX de qualquer tipo. CPF e CNPJ passam pelos validadores de documento.
+pub fn validar_chave_pix(chave: &str, tipo: Option<TipoChavePix>) -> ValidacaoChavePix {
+    let chave = chave.trim();
+    let Some(tipo) = tipo.or_else(|| detectar_tipo(chave)) else {
+        return chave_invalida(None, "Tipo de chave não reconhecido");
+    };
+
+    match tipo {
+        TipoChavePix::Cpf => {
+            let relatorio = validar_cpf(chave);
+            if relatorio.valid {
+                chave_valida(tipo, relatorio.normalized)
+            } else {
+                chave_invalida(Some(tipo), "CPF inválido")
+            }
+        }
+        TipoChavePix::Cnpj => {
+            let relatorio = validar_cnpj(chave);
+            if relatorio.valid {
+                chave_valida(tipo, relatorio.normalized)
+            } else {
+                chave_invalida(Some(tipo), "CNPJ inválido")
+            }
+        }
+        TipoChavePix::Telefone => validar_telefone(chave),
+        TipoChavePix::Email => validar_email(chave),
+        TipoChavePix::Aleatoria => {
+            if eh_uuid(chave) {
+                chave_valida(tipo, chave.to_lowercase())
+            } else {
+                chave_invalida(Some(tipo), "Chave aleatória deve ser um UUID")
+            }
+        }
+    }
+}
+
+/// CRC16-CCITT (polinômio 0x1021, valor inicial 0xFFFF), exigido no campo 63.
+pub fn crc16(dados: &[u8]) -> u16 {
+    dados.iter().fold(0xFFFF, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// O BR Code só aceita ASCII: troca letras acentuadas pela versão sem acento.
+fn remover_acentos(texto: &str) -> String {
+    const COM_ACENTO: &str = "áàâãäéèêëíìîïóòôõöúùûüçñÁÀÂÃÄÉÈÊËÍÌÎÏÓÒÔÕÖÚÙÛÜÇÑ";
+    const SEM_ACENTO: &str = "aaaaaeeeeiiiiooooouuuucnAAAAAEEEEIIIIOOOOOUUUUCN";
+    texto
+        .chars()
+        .map(|c| match COM_ACENTO.chars().position(|a| a == c) {
+            Some(i) => SEM_ACENTO.chars().nth(i).unwrap(),
+            None => c,
+        })
+        .collect()
+}
+
+/// Campo EMV: o tamanho conta bytes, e `ler_tlv` conta caracteres; só coincidem em ASCII.
+fn tlv(id: &str, valor: &str) -> String {
+    assert!(valor.is_ascii(), "campo {} do BR Code fora do ASCII", id);
+    format!("{}{:02}{}", id, valor.len(), valor)
+}
+
+fn formatar_valor(centavos: u64) -> String {
+    format!("{}.{:02}", centavos / 100, centavos % 100)
+}
+
+fn texto_ascii(campo: &str, valor: &str, maximo: usize) -> Result<String, String> {
+    let valor = remover_acentos(valor.trim());
+    if valor.is_empty() || valor.len() > maximo {
+        return Err(format!("{} deve ter de 1 a {} caracteres", campo, maximo));
+    }
+    if !valor.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        return Err(format!("{} contém caracteres não suportados", campo));
+    }
+    Ok(valor)
+}
+
+/// Monta o copia e cola de um BR Code estático (EMV TLV), com o CRC16 no final.
+pub fn gerar_br_code(dados: &DadosBrCode) -> Result<String, String> {
+    let chave = validar_chave_pix(&dados.chave, None);
+    let Some(chave) = chave.normalizada else {
+        return Err(chave.erro.unwrap_or_else(|| "Chave PIX inválida".into()));
+    };
+    if !chave.is_ascii() {
+        return Err("Chave PIX com caracteres não suportados no BR Code".into());
+    }
+    let nome = texto_ascii("Nome", &dados.nome, MAX_NOME)?;
+    let cidade = texto_ascii("Cidade", &dados.cidade, MAX_CIDADE)?;
+    let txid = match dados.txid.as_deref() {
+        Some(txid) if txid.is_empty() || txid.len() > MAX_TXID => {
+            return Err(format!("txid deve ter de 1 a {} caracteres", MAX_TXID))
+        }
+        Some(txid) if !txid.chars().all(|c| c.is_ascii_alphanumeric()) => {
+            return Err("txid deve conter apenas letras e dígitos".into())
+        }
+        Some(txid) => txid,
+        None => TXID_AUSENTE,
+    };
+
+    let mut conta = tlv("00", GUI_PIX) + &tlv("01", &chave);
+    if let Some(descricao) = dados.descricao.as_deref().filter(|d| !d.trim().is_empty()) {
+        conta += &tlv("02", &texto_ascii("Descrição", descricao, MAX_CAMPO)?);
+    }
+    if conta.len() > MAX_CAMPO {
+        return Err("Chave e descrição somam mais de 99 caracteres".into());
+    }
+
+    let mut payload = tlv("00", "01") + &tlv("26", &conta) + &tlv("52", "0000") + &tlv("53", "986");
+    if let Some(centavos) = dados.valor_centavos {
+        let valor = formatar_valor(centavos);
+        if valor.len() > MAX_VALOR {
+            return Err("Valor acima do permitido".into());
+        }
+        payload += &tlv("54", &valor);
+    }
+    payload += &tlv("58", "BR");
+    payload += &tlv("59", &nome);
+    payload += &tlv("60", &cidade);
+    payload += &tlv("62", &tlv("05", txid));
+    payload += "6304";
+
+    let crc = crc16(payload.as_bytes());
+    Ok(format!("{}{:04X}", payload, crc))
+}
+
+fn nome_campo(id: &str) -> &'static str {
+    match id.parse::<u8>().unwrap_or(u8::MAX) {
+        0 => "Indicador de formato",
+        1 => "Método de iniciação",
+        26 => "Conta do recebedor (PIX)",
+        27..=51 => "Conta do recebedor",
+        52 => "Categoria do estabelecimento (MCC)",
+        53 => "Moeda",
+        54 => "Valor",
+        58 => "País",
+        59 => "Nome do recebedor",
+        60 => "Cidade",
+        61 => "CEP",
+        62 => "Dados adicionais",
+        63 => "CRC16",
+        80..=99 => "Template livre",
+        _ => "Desconhecido",
+    }
+}
+
+fn nome_subcampo(pai: &str, id: &str) -> &'static str {
+    match (pai, id) {
+        (_, "00") => "Identificador do arranjo (GUI)",
+        ("26", "01") => "Chave",
+        ("26", "02") => "Informação adicional",
+        ("26", "25") => "URL do payload dinâmico",
+        ("62", "05") => "Identificador da transação (txid)",
+        _ => "Desconhecido",
+    }
+}
+
+/// Quebra uma sequência TLV (id e tamanho com 2 dígitos cada) em pares id/valor.
+fn ler_tlv(texto: &str) -> Result<Vec<(usize, String, String)>, String> {
+    let chars: Vec<char> = texto.chars().collect();
+    let mut campos = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let cabecalho: String = chars.get(i..i + 4).unwrap_or_default().iter().collect();
+        if cabecalho.len() != 4 || !cabecalho.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Cabeçalho de campo inválido na posição {}", i));
+        }
+        let id = cabecalho[..2].to_string();
+        let tamanho: usize = cabecalho[2..].parse().unwrap();
+        let Some(valor) = chars.get(i + 4..i + 4 + tamanho) else {
+            return Err(format!("Campo {} ultrapassa o fim do payload", id));
+        };
+        campos.push((i, id, valor.iter().collect()));
+        i += 4 + tamanho;
+    }
+
+    Ok(campos)
+}
+
+fn tem_subcampos(id: &str) -> bool {
+    matches!(id.parse::<u8>(), Ok(26..=51 | 62 | 80..=99))
+}
+
+fn validar_campo(id: &str, valor: &str, subcampos: &[CampoBrCode]) -> Option<String> {
+    let subcampo = |sub: &str| {
+        subcampos
+            .iter()
+            .find(|c| c.id == sub)
+            .map(|c| c.valor.as_str())
+    };
+
+    match id {
+        "00" if valor != "01" => Some("Deve ser 01".into()),
+        "01" if valor != "11" && valor != "12" => {
+            Some("Deve ser 11 (reutilizável) ou 12 (único)".into())
+        }
+        "26" => match (subcampo("00"), subcampo("01"), subcampo("25")) {
+            (Some(gui), _, _) if !gui.eq_ignore_ascii_case(GUI_PIX) => {
+                Some(format!("GUI deve ser {}", GUI_PIX))
+            }
+            (None, _, _) => Some("GUI ausente".into()),
+            (_, None, None) => Some("Sem chave (01) nem URL (25)".into()),
+            (_, Some(chave), _) => validar_chave_pix(chave, None).erro,
+            _ => None,
+        },
+        "52" if valor.len() != 4 || !valor.chars().all(|c| c.is_ascii_digit()) => {
+            Some("Deve ter 4 dígitos".into())
+        }
+        "53" if valor != "986" => Some("Moeda deve ser 986 (real)".into()),
+        "54" if valor_em_centavos(valor).is_none() || valor.len() > MAX_VALOR => {
+            Some("Valor deve ter até 2 casas decimais separadas por ponto".into())
+        }
+        "58" if valor != "BR" => Some("País deve ser BR".into()),
+        "59" if valor.is_empty() || valor.chars().count() > MAX_NOME => {
+            Some(format!("Deve ter de 1 a {} caracteres", MAX_NOME))
+        }
+        "60" if valor.is_empty() || valor.chars().count() > MAX_CIDADE => {
+            Some(format!("Deve ter de 1 a {} caracteres", MAX_CIDADE))
+        }
+        "62" => match subcampo("05") {
+            None => Some("txid ausente".into()),
+            Some(txid) if txid.is_empty() || txid.len() > MAX_TXID => {
+                Some(format!("txid deve ter de 1 a {} caracteres", MAX_TXID))
+            }
+            Some(txid)
+                if txid != TXID_AUSENTE && !txid.chars().all(|c| c.is_ascii_alphanumeric()) =>
+            {
+                Some("txid deve conter apenas letras e dígitos".into())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn valor_em_centavos(valor: &str) -> Option<u64> {
+    let (inteiro, decimal) = valor.split_once('.').unwrap_or((valor, ""));
+    let so_digitos = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if inteiro.is_empty() || !so_digitos(inteiro) || decimal.len() > 2 || !so_digitos(decimal) {
+        return None;
+    }
+    let centavos: u64 = format!("{:0<2}", decimal).parse().ok()?;
+    Some(inteiro.parse::<u64>().ok()? * 100 + centavos)
+}
+
+/// Lê um copia e cola PIX campo a campo, conferindo formato, campos obrigatórios e CRC16.
+pub fn ler_br_code(payload: &str) -> LeituraBrCode {
+    let payload = payload.trim();
+    let mut leitura = LeituraBrCode {
+        valido: false,
+        campos: Vec::new(),
+        erros: Vec::new(),
+        crc_esperado: None,
+        crc_informado: None,
+        dados: None,
+    };
+
+    let brutos = match ler_tlv(payload) {
+        Ok(brutos) => brutos,
+        Err(erro) => {
+            leitura.erros.push(erro);
+            return leitura;
+        }
+    };
+
+    for (posicao, id, valor) in brutos {
+        let subcampos = if tem_subcampos(&id) {
+            ler_tlv(&valor)
+                .map(|subs| {
+                    subs.into_iter()
+                        .map(|(_, sub, valor)| CampoBrCode {
+                            nome: nome_subcampo(&id, &sub).into(),
+                            id: sub,
+                            valor,
+                            subcampos: Vec::new(),
+                            erro: None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let mut erro = validar_campo(&id, &valor, &subcampos);
+        if id == "63" {
+            let prefixo: String = payload.chars().take(posicao + 4).collect();
+            let esperado = format!("{:04X}", crc16(prefixo.as_bytes()));
+            if !valor.eq_ignore_ascii_case(&esperado) {
+                erro = Some(format!("CRC esperado {}, informado {}", esperado, valor));
+            }
+            leitura.crc_esperado = Some(esperado);
+            leitura.crc_informado = Some(valor.clone());
+        }
+
+        leitura.campos.push(CampoBrCode {
+            nome: nome_campo(&id).into(),
+            id,
+            valor,
+            subcampos,
+            erro,
+        });
+    }
+
+    let ids: Vec<&str> = leitura.campos.iter().map(|c| c.id.as_str()).collect();
+    if ids.first() != Some(&"00") {
+        leitura.erros.push("O campo 00 deve ser o primeiro".into());
+    }
+    if ids.last() != Some(&"63") {
+        leitura
+            .erros
+            .push("O campo 63 (CRC16) deve ser o último".into());
+    }
+    for obrigatorio in ["00", "26", "52", "53", "58", "59", "60", "63"] {
+        if !ids.contains(&obrigatorio) {
+            leitura.erros.push(format!(
+                "Campo obrigatório ausente: {} ({})",
+                obrigatorio,
+                nome_campo(obrigatorio)
+            ));
+        }
+    }
+
+    leitura.dados = extrair_dados(&leitura.campos);
+    leitura.valido = leitura.erros.is_empty() && leitura.campos.iter().all(|c| c.erro.is_none());
+    leitura
+}
+
+fn extrair_dados(campos: &[CampoBrCode]) -> Option<DadosBrCode> {
+    let campo = |id: &str| campos.iter().find(|c| c.id == id);
+    let subcampo = |id: &str, sub: &str| {
+        campo(id)?
+            .subcampos
+            .iter()
+            .find(|c| c.id == sub)
+            .map(|c| c.valor.clone())
+    };
+
+    Some(DadosBrCode {
+        chave: subcampo("26", "01")?,
+        nome: campo("59")?.valor.clone(),
+        cidade: campo("60")?.valor.clone(),
+        valor_centavos: campo("54").and_then(|c| valor_em_centavos(&c.valor)),
+        txid: subcampo("62", "05").filter(|txid| txid != TXID_AUSENTE),
+        descricao: subcampo("26", "02"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exemplo do manual do BR Code do Banco Central.
+    const EXEMPLO_BCB: &str = "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D";
+
+    fn dados(chave: &str) -> DadosBrCode {
+        DadosBrCode {
+            chave: chave.to_string(),
+            nome: "Fulano de Tal".to_string(),
+            cidade: "BRASILIA".to_string(),
+            valor_centavos: None,
+            txid: None,
+            descricao: None,
+        }
+    }
+
+    #[test]
+    fn valida_e_normaliza_cada_tipo_de_chave() {
+        let casos = [
+            ("529.982.247-25", TipoChavePix::Cpf, "52998224725"),
+            ("12.ABC.345/01DE-35", TipoChavePix::Cnpj, "12ABC34501DE35"),
+            (
+                "+55 (11) 98765-4321",
+                TipoChavePix::Telefone,
+                "+5511987654321",
+            ),
+            (
+                "Fulano@Example.com",
+                TipoChavePix::Email,
+                "fulano@example.com",
+            ),
+            (
+                "123E4567-E12B-12D1-A456-426655440000",
+                TipoChavePix::Aleatoria,
+                "123e4567-e12b-12d1-a456-426655440000",
+            ),
+        ];
+        for (chave, tipo, normalizada) in casos {
+            let validacao = validar_chave_pix(chave, None);
+            assert!(validacao.valida, "{}: {:?}", chave, validacao.erro);
+            assert_eq!(validacao.tipo, Some(tipo));
+            assert_eq!(validacao.normalizada.as_deref(), Some(normalizada));
+        }
+
+        for chave in ["529.982.247-24", "11987654321@", "+5501987654321", "abc"] {
+            assert!(!validar_chave_pix(chave, None).valida, "{}", chave);
+        }
+        assert!(!validar_chave_pix("5511987654321", Some(TipoChavePix::Telefone)).valida);
+    }
+
+    #[test]
+    fn gera_o_exemplo_do_banco_central() {
+        let payload = gerar_br_code(&dados("123e4567-e12b-12d1-a456-426655440000")).unwrap();
+        assert_eq!(payload, EXEMPLO_BCB);
+        assert_eq!(format!("{:04X}", crc16(b"123456789")), "29B1");
+    }
+
+    #[test]
+    fn le_o_exemplo_e_acusa_crc_errado() {
+        let leitura = ler_br_code(EXEMPLO_BCB);
+        assert!(leitura.valido, "{:?}", leitura.erros);
+        assert_eq!(leitura.crc_esperado.as_deref(), Some("1D3D"));
+        let lidos = leitura.dados.unwrap();
+        assert_eq!(lidos.nome, "Fulano de Tal");
+        assert_eq!(lidos.txid, None);
+
+        let adulterado = EXEMPLO_BCB.replace("Fulano", "Fulana");
+        let leitura = ler_br_code(&adulterado);
+        assert!(!leitura.valido);
+        assert_eq!(leitura.crc_informado.as_deref(), Some("1D3D"));
+        assert_ne!(leitura.crc_esperado.as_deref(), Some("1D3D"));
+    }
+
+    #[test]
+    fn descricao_passa_pela_mesma_limpeza_de_nome_e_cidade() {
+        let mut entrada = dados("fulano@example.com");
+        entrada.nome = "José Ninguém".to_string();
+        entrada.cidade = "São Paulo".to_string();
+        entrada.descricao = Some(" Pagamento de ação ".to_string());
+        entrada.valor_centavos = Some(1050);
+        entrada.txid = Some("PEDIDO42".to_string());
+
+        let payload = gerar_br_code(&entrada).unwrap();
+        assert!(payload.is_ascii());
+        let leitura = ler_br_code(&payload);
+        assert!(leitura.valido, "{:?}", leitura.erros);
+        let lidos = leitura.dados.unwrap();
+        assert_eq!(lidos.nome, "Jose Ninguem");
+        assert_eq!(lidos.cidade, "Sao Paulo");
+        assert_eq!(lidos.descricao.as_deref(), Some("Pagamento de acao"));
+        assert_eq!(lidos.valor_centavos, Some(1050));
+        assert_eq!(lidos.txid.as_deref(), Some("PEDIDO42"));
+
+        entrada.descricao = Some("Obrigado 🎉".to_string());
+        assert!(gerar_br_code(&entrada).is_err());
+        entrada.descricao = Some("x".repeat(80));
+        assert!(gerar_br_code(&entrada).is_err());
+    }
+
+    #[test]
+    fn rejeita_chave_e_campos_fora_do_padrao() {
+        assert!(gerar_br_code(&dados("joão@example.com")).is_err());
+        assert!(gerar_br_code(&dados("529.982.247-24")).is_err());
+
+        let mut entrada = dados("52998224725");
+        entrada.cidade = "Santo Antônio do Descoberto".to_string();
+        assert!(gerar_br_code(&entrada).is_err());
+
+        let mut entrada = dados("52998224725");
+        entrada.txid = Some("pedido-42".to_string());
+        assert!(gerar_br_code(&entrada).is_err());
+    }
+}
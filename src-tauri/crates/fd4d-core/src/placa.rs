@@ -0,0 +1,237 @@
+use crate::cpf_cnpj::GeradorDocumentos;
+use crate::documentos::sortear_renavam;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FormatoPlaca {
+    /// Padrão anterior: `AAA-9999`.
+    Antiga,
+    /// Padrão Mercosul: `AAA9A99`.
+    Mercosul,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacaGerada {
+    pub placa: String,
+    pub formato: FormatoPlaca,
+    /// A mesma placa no outro formato, quando a conversão existe.
+    pub equivalente: Option<String>,
+    pub renavam: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidacaoPlaca {
+    pub valida: bool,
+    pub formato: Option<FormatoPlaca>,
+    pub normalizada: String,
+    pub formatada: Option<String>,
+    pub equivalente: Option<String>,
+    pub erro: Option<String>,
+}
+
+/// Na conversão oficial, o segundo dígito da placa antiga vira a letra de mesma posição
+/// no alfabeto (0 = A ... 9 = J) e vice-versa.
+fn digito_para_letra(digito: char) -> char {
+    (b'A' + digito.to_digit(10).unwrap() as u8) as char
+}
+
+fn letra_para_digito(letra: char) -> Option<char> {
+    ('A'..='J')
+        .contains(&letra)
+        .then(|| char::from_digit(letra as u32 - 'A' as u32, 10).unwrap())
+}
+
+fn casa_padrao(padrao: &str, placa: &str) -> bool {
+    placa.len() == padrao.len()
+        && padrao.chars().zip(placa.chars()).all(|(p, c)| match p {
+            'A' => c.is_ascii_uppercase(),
+            '9' => c.is_ascii_digit(),
+            _ => false,
+        })
+}
+
+fn formato_da_placa(placa: &str) -> Option<FormatoPlaca> {
+    if casa_padrao("AAA9999", placa) {
+        Some(FormatoPlaca::Antiga)
+    } else if casa_padrao("AAA9A99", placa) {
+        Some(FormatoPlaca::Mercosul)
+    } else {
+        None
+    }
+}
+
+fn formatar(placa: &str, formato: FormatoPlaca, com_mascara: bool) -> String {
+    match formato {
+        FormatoPlaca::Antiga if com_mascara => format!("{}-{}", &placa[..3], &placa[3..]),
+        _ => placa.to_string(),
+    }
+}
+
+/// Placa normalizada (sem hífen) no outro formato. Placas Mercosul com a 5ª letra
+/// depois do J não têm equivalente antiga.
+fn converter(placa: &str, formato: FormatoPlaca) -> Option<String> {
+    let mut chars: Vec<char> = placa.chars().collect();
+    chars[4] = match formato {
+        FormatoPlaca::Antiga => digito_para_letra(chars[4]),
+        FormatoPlaca::Mercosul => letra_para_digito(chars[4])?,
+    };
+    Some(chars.into_iter().collect())
+}
+
+fn outro_formato(formato: FormatoPlaca) -> FormatoPlaca {
+    match formato {
+        FormatoPlaca::Antiga => FormatoPlaca::Mercosul,
+        FormatoPlaca::Mercosul => FormatoPlaca::Antiga,
+    }
+}
+
+/// Aceita os dois formatos, com ou sem hífen e em qualquer caixa.
+pub fn validar_placa(placa_input: &str) -> ValidacaoPlaca {
+    let normalizada: String = placa_input
+        .trim()
+        .chars()
+        .filter(|&c| c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut validacao = ValidacaoPlaca {
+        valida: false,
+        formato: None,
+        normalizada: normalizada.clone(),
+        formatada: None,
+        equivalente: None,
+        erro: None,
+    };
+
+    let Some(formato) = formato_da_placa(&normalizada) else {
+        validacao.erro = Some("Placa deve estar no formato AAA-9999 ou AAA9A99".into());
+        return validacao;
+    };
+    validacao.formato = Some(formato);
+
+    if formato == FormatoPlaca::Antiga && &normalizada[3..] == "0000" {
+        validacao.erro = Some("Placa antiga não pode ter o número 0000".into());
+        return validacao;
+    }
+
+    validacao.valida = true;
+    validacao.formatada = Some(formatar(&normalizada, formato, true));
+    validacao.equivalente = converter(&normalizada, formato)
+        .map(|equivalente| formatar(&equivalente, outro_formato(formato), true));
+    validacao
+}
+
+/// Converte uma placa antiga para Mercosul ou uma Mercosul para antiga.
+pub fn converter_placa(placa_input: &str) -> Result<String, String> {
+    let validacao = validar_placa(placa_input);
+    if !validacao.valida {
+        return Err(validacao.erro.unwrap_or_else(|| "Placa inválida".into()));
+    }
+    validacao
+        .equivalente
+        .ok_or_else(|| "Placa Mercosul com 5ª letra após o J não tem equivalente antiga".into())
+}
+
+/// Gera uma placa no formato pedido (Mercosul, se omitido). Com `com_renavam`, sorteia
+/// também um RENAVAM válido para o veículo.
+pub fn gerar_placa(
+    formato: Option<FormatoPlaca>,
+    com_mascara: bool,
+    com_renavam: bool,
+    semente: Option<u64>,
+) -> PlacaGerada {
+    let formato = formato.unwrap_or(FormatoPlaca::Mercosul);
+    let mut gerador = GeradorDocumentos::new(semente);
+    let rng = gerador.rng();
+
+    let letras: String = (0..3).map(|_| rng.random_range('A'..='Z')).collect();
+    let numero = rng.random_range(1..=9999u32);
+    let antiga = format!("{}{:04}", letras, numero);
+    let placa = match formato {
+        FormatoPlaca::Antiga => antiga,
+        FormatoPlaca::Mercosul => converter(&antiga, FormatoPlaca::Antiga).unwrap(),
+    };
+
+    PlacaGerada {
+        equivalente: converter(&placa, formato)
+            .map(|equivalente| formatar(&equivalente, outro_formato(formato), com_mascara)),
+        placa: formatar(&placa, formato, com_mascara),
+        formato,
+        renavam: com_renavam.then(|| sortear_renavam(rng)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documentos::validar_renavam;
+
+    #[test]
+    fn valida_e_converte_os_dois_formatos() {
+        let antiga = validar_placa(" abc-1234 ");
+        assert!(antiga.valida);
+        assert_eq!(antiga.formato, Some(FormatoPlaca::Antiga));
+        assert_eq!(antiga.normalizada, "ABC1234");
+        assert_eq!(antiga.formatada.as_deref(), Some("ABC-1234"));
+        assert_eq!(antiga.equivalente.as_deref(), Some("ABC1C34"));
+
+        let mercosul = validar_placa("ABC1C34");
+        assert!(mercosul.valida);
+        assert_eq!(mercosul.formato, Some(FormatoPlaca::Mercosul));
+        assert_eq!(mercosul.equivalente.as_deref(), Some("ABC-1234"));
+
+        assert_eq!(converter_placa("BRA2E19").unwrap(), "BRA-2419");
+        assert_eq!(converter_placa("BRA-2419").unwrap(), "BRA2E19");
+    }
+
+    #[test]
+    fn rejeita_placas_fora_do_padrao() {
+        for placa in ["", "AB1234", "ABC12345", "1BC1234", "ABC1C3D", "ABC-1C34X"] {
+            let validacao = validar_placa(placa);
+            assert!(!validacao.valida, "{}", placa);
+            assert!(validacao.formato.is_none(), "{}", placa);
+        }
+
+        let zerada = validar_placa("ABC-0000");
+        assert!(!zerada.valida);
+        assert_eq!(zerada.formato, Some(FormatoPlaca::Antiga));
+        assert!(converter_placa("ABC0000").is_err());
+    }
+
+    #[test]
+    fn mercosul_depois_do_j_nao_tem_antiga() {
+        let validacao = validar_placa("ABC1K34");
+        assert!(validacao.valida);
+        assert!(validacao.equivalente.is_none());
+        assert!(converter_placa("ABC1K34").is_err());
+    }
+
+    #[test]
+    fn placas_geradas_passam_na_validacao() {
+        for semente in 0..200 {
+            for formato in [FormatoPlaca::Antiga, FormatoPlaca::Mercosul] {
+                let gerada = gerar_placa(Some(formato), semente % 2 == 0, true, Some(semente));
+                let validacao = validar_placa(&gerada.placa);
+                assert!(validacao.valida, "{}", gerada.placa);
+                assert_eq!(validacao.formato, Some(formato));
+                let equivalente = gerada.equivalente.unwrap();
+                assert_eq!(
+                    converter_placa(&gerada.placa).unwrap().replace('-', ""),
+                    equivalente.replace('-', "")
+                );
+                assert!(validar_renavam(&gerada.renavam.unwrap()).valid);
+            }
+        }
+
+        let padrao = gerar_placa(None, false, false, Some(1));
+        assert_eq!(padrao.formato, FormatoPlaca::Mercosul);
+        assert!(padrao.renavam.is_none());
+        assert_eq!(
+            gerar_placa(None, true, true, Some(7)).placa,
+            gerar_placa(None, true, true, Some(7)).placa
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// Regra que reprovou o documento na validação.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidationFailure {
+    WrongLength,
+    InvalidCharacters,
+    InvalidMask,
+    InvalidPrefix,
+    InvalidUfCode,
+    RepeatedDigits,
+    FirstCheckDigitMismatch,
+    SecondCheckDigitMismatch,
+    /// DV de um dos campos da linha digitável do boleto.
+    FieldCheckDigitMismatch,
+    ChecksumMismatch,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub failure: Option<ValidationFailure>,
+    pub expected_check_digits: Option<String>,
+    pub supplied_check_digits: Option<String>,
+    pub normalized: String,
+    pub masked: Option<String>,
+    pub was_masked: bool,
+    pub likely_ufs: Vec<String>,
+}
+
+impl ValidationReport {
+    pub(crate) fn new(input: &str, normalized: String) -> Self {
+        ValidationReport {
+            valid: false,
+            failure: None,
+            expected_check_digits: None,
+            supplied_check_digits: None,
+            normalized,
+            masked: None,
+            was_masked: input.trim().chars().any(|c| !c.is_ascii_alphanumeric()),
+            likely_ufs: Vec::new(),
+        }
+    }
+
+    pub(crate) fn fail(mut self, failure: ValidationFailure) -> Self {
+        self.failure = Some(failure);
+        self
+    }
+
+    pub(crate) fn pass(mut self) -> Self {
+        self.valid = true;
+        self
+    }
+}
+
+/// A pontuação não precisa seguir a máscara canônica (`529982247-25` é aceito); só
+/// reprova caracteres que não são separadores de documento (`.`, `-`, `/` e espaços).
+pub(crate) fn mascara_valida(input: &str) -> bool {
+    input
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c.is_whitespace() || matches!(c, '.' | '-' | '/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relatorio_registra_se_a_entrada_tinha_mascara() {
+        assert!(ValidationReport::new("529.982.247-25", String::new()).was_masked);
+        assert!(!ValidationReport::new(" 52998224725 ", String::new()).was_masked);
+    }
+
+    #[test]
+    fn fail_e_pass_definem_o_resultado() {
+        let report = ValidationReport::new("1", "1".into());
+        assert!(!report.valid);
+        let report = report.fail(ValidationFailure::WrongLength);
+        assert_eq!(report.failure, Some(ValidationFailure::WrongLength));
+        assert!(ValidationReport::new("1", "1".into()).pass().valid);
+    }
+
+    #[test]
+    fn mascara_aceita_qualquer_pontuacao_de_separador() {
+        assert!(mascara_valida("12ABC34501DE35"));
+        assert!(mascara_valida("12.abc.345/01de-35"));
+        assert!(mascara_valida("12ABC345/01DE-35"));
+        assert!(mascara_valida(" 12.ABC.345-01DE/35 "));
+        assert!(!mascara_valida("12,ABC,345/01DE-35"));
+        assert!(!mascara_valida("12ABC34501DE_35"));
+    }
+}
@@ -0,0 +1,657 @@
+use crate::webhook_faults::{FaultConfig, InjectedFault, MAX_HANG};
+use crate::webhook_rules::{MockResponse, ResponseRule};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Json, MatchedPath, RawQuery, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    routing::{any, get},
+    Router,
+};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tower_http::cors::CorsLayer;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookRequest {
+    pub id: String,
+    pub timestamp: String,
+    pub method: String,
+    pub path: String,
+    /// Parâmetros da query string; com chaves repetidas, fica o último valor.
+    pub query: HashMap<String, String>,
+    /// Padrão de rota que atendeu a requisição (ex.: `/*path`).
+    pub route: Option<String>,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub status_code: u16,
+    pub error_reason: Option<String>,
+    /// Id da [`ResponseRule`] que gerou a resposta; `None` na resposta padrão.
+    pub matched_rule: Option<String>,
+    /// Falhas simuladas aplicadas a esta requisição, na ordem em que ocorreram.
+    #[serde(default)]
+    pub faults: Vec<InjectedFault>,
+}
+
+/// Notificações do receptor: cada captura e as mudanças de estado do servidor.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WebhookEvent {
+    Request(Box<WebhookRequest>),
+    Started { url: String },
+    Stopped,
+    BindError { error: String },
+    Crashed { error: String },
+}
+
+type WebhookListener = Arc<dyn Fn(&WebhookEvent) + Send + Sync>;
+
+#[derive(Clone, Default)]
+struct WebhookAuth {
+    method: String,
+    value: String,
+}
+
+/// Credenciais, regras e falhas do receptor. Clonar compartilha o mesmo estado, então quem
+/// embute o servidor pode reconfigurá-lo enquanto ele roda; as capturas chegam pelo listener.
+#[derive(Clone, Default)]
+pub struct WebhookCapture {
+    auth: Arc<Mutex<WebhookAuth>>,
+    listener: Arc<Mutex<Option<WebhookListener>>>,
+    rules: Arc<Mutex<Vec<ResponseRule>>>,
+    faults: Arc<Mutex<FaultConfig>>,
+    /// Requisições vistas por configuração de falhas (`""` é a global, senão o id da regra),
+    /// para o `fail_first`.
+    attempts: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl WebhookCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define o método (`bearer`, `api-key` ou `basic`) e o valor exigidos.
+    /// Com qualquer um dos dois vazio, todas as requisições são aceitas.
+    pub fn set_auth(&self, method: String, value: String) {
+        *self.auth.lock().unwrap() = WebhookAuth { method, value };
+    }
+
+    /// Registra quem recebe os [`WebhookEvent`]s, substituindo o anterior.
+    /// É chamado na thread que gerou o evento, então não deve bloquear.
+    pub fn set_listener(&self, listener: impl Fn(&WebhookEvent) + Send + Sync + 'static) {
+        *self.listener.lock().unwrap() = Some(Arc::new(listener));
+    }
+
+    fn emit(&self, event: WebhookEvent) {
+        let listener = self.listener.lock().unwrap().clone();
+        if let Some(listener) = listener {
+            listener(&event);
+        }
+    }
+
+    /// Define as respostas simuladas; vale a primeira regra ativa que casar com a requisição.
+    /// Requisições recusadas pela autenticação continuam recebendo 401.
+    pub fn set_rules(&self, rules: Vec<ResponseRule>) {
+        *self.rules.lock().unwrap() = rules;
+        self.reset_fault_counters();
+    }
+
+    pub fn rules(&self) -> Vec<ResponseRule> {
+        self.rules.lock().unwrap().clone()
+    }
+
+    /// Falhas aplicadas quando nenhuma regra com falhas próprias atende a requisição.
+    /// Assim como as regras, não afetam requisições recusadas pela autenticação.
+    pub fn set_faults(&self, faults: FaultConfig) {
+        *self.faults.lock().unwrap() = faults;
+        self.reset_fault_counters();
+    }
+
+    pub fn faults(&self) -> FaultConfig {
+        self.faults.lock().unwrap().clone()
+    }
+
+    /// Recomeça a contagem do `fail_first` de todas as configurações.
+    pub fn reset_fault_counters(&self) {
+        self.attempts.lock().unwrap().clear();
+    }
+
+    fn plan_faults(&self, key: &str, faults: &FaultConfig) -> Vec<InjectedFault> {
+        let attempt = {
+            let mut attempts = self.attempts.lock().unwrap();
+            let attempt = attempts.entry(key.to_string()).or_default();
+            *attempt = attempt.saturating_add(1);
+            *attempt
+        };
+        faults.plan(attempt, &mut rand::rng())
+    }
+
+    /// `GET /health` e, para qualquer outro caminho e método, o receptor de webhooks.
+    /// Pronto para servir ou montar em outro router.
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/health", get(health_check))
+            .route("/", any(handle_webhook))
+            .route("/*path", any(handle_webhook))
+            .layer(CorsLayer::permissive())
+            .with_state(self.clone())
+    }
+}
+
+async fn handle_webhook(
+    State(state): State<WebhookCapture>,
+    method: Method,
+    uri: Uri,
+    route: Option<MatchedPath>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let WebhookAuth {
+        method: auth_method,
+        value: auth_value,
+    } = state.auth.lock().unwrap().clone();
+
+    let mut status_code = 200;
+    let mut error_reason: Option<String> = None;
+
+    if !auth_method.is_empty() && !auth_value.is_empty() {
+        let auth_valid = match auth_method.as_str() {
+            "bearer" => {
+                if let Some(auth_header) = headers.get("authorization") {
+                    if let Ok(value) = auth_header.to_str() {
+                        value == format!("Bearer {}", auth_value) || value == auth_value
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            "api-key" => {
+                if let Some(api_key) = headers.get("x-api-key") {
+                    if let Ok(value) = api_key.to_str() {
+                        value == auth_value
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            "basic" => {
+                if let Some(auth_header) = headers.get("authorization") {
+                    if let Ok(value) = auth_header.to_str() {
+                        value == format!("Basic {}", auth_value) || value == auth_value
+                    } else {
+                        false
+                    }
+                } else {
+                    false
+                }
+            }
+            _ => true,
+        };
+
+        if !auth_valid {
+            status_code = 401;
+            error_reason = Some("Authentication failed: Invalid credentials".to_string());
+        }
+    }
+
+    let headers_map: HashMap<String, String> = headers
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+        .collect();
+
+    let mut request = WebhookRequest {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        method: method.to_string(),
+        path: uri.path().to_string(),
+        query: query
+            .and_then(|query| serde_urlencoded::from_str::<Vec<(String, String)>>(&query).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        route: route.map(|route| route.as_str().to_string()),
+        headers: headers_map,
+        body: String::from_utf8_lossy(&body).into_owned(),
+        status_code,
+        error_reason: error_reason.clone(),
+        matched_rule: None,
+        faults: Vec::new(),
+    };
+
+    let json_body = serde_json::from_str::<serde_json::Value>(&request.body).ok();
+    let matched = (status_code == 200)
+        .then(|| {
+            let rules = state.rules.lock().unwrap();
+            rules
+                .iter()
+                .find(|rule| rule.matches(&request, json_body.as_ref()))
+                .map(|rule| {
+                    (
+                        rule.id.clone(),
+                        rule.render(&request, json_body.as_ref()),
+                        rule.faults.clone(),
+                    )
+                })
+        })
+        .flatten();
+
+    let faults = if status_code == 200 {
+        match &matched {
+            Some((rule_id, _, Some(faults))) => state.plan_faults(rule_id, faults),
+            _ => state.plan_faults("", &state.faults()),
+        }
+    } else {
+        Vec::new()
+    };
+    let forced_status = faults.iter().find_map(InjectedFault::status_code);
+
+    let mut response = match (forced_status, matched) {
+        (Some(forced_status), matched) => {
+            let reason = format!("Injected fault: HTTP {}", forced_status);
+            request.status_code = forced_status;
+            request.error_reason = Some(reason.clone());
+            request.matched_rule = matched.map(|(rule_id, _, _)| rule_id);
+            json_response(
+                forced_status,
+                serde_json::json!({ "success": false, "error": reason }),
+            )
+        }
+        (None, Some((rule_id, mock, _))) => {
+            request.status_code = mock.status_code;
+            request.matched_rule = Some(rule_id);
+            if mock.status_code >= 400 {
+                request.error_reason = Some(format!("Mock response: HTTP {}", mock.status_code));
+            }
+            mock_response(mock)
+        }
+        (None, None) if status_code == 200 => json_response(
+            status_code,
+            serde_json::json!({
+                "success": true,
+                "message": "Webhook received successfully"
+            }),
+        ),
+        (None, None) => json_response(
+            status_code,
+            serde_json::json!({
+                "success": false,
+                "error": error_reason.unwrap_or_else(|| "Unknown error".to_string())
+            }),
+        ),
+    };
+
+    request.faults = faults.clone();
+    state.emit(WebhookEvent::Request(Box::new(request)));
+
+    for fault in faults {
+        match fault {
+            InjectedFault::Delay { ms } => {
+                tokio::time::sleep(std::time::Duration::from_millis(ms)).await
+            }
+            InjectedFault::Hang => tokio::time::sleep(MAX_HANG).await,
+            InjectedFault::DropConnection => response = drop_midway(response).await,
+            InjectedFault::Error { .. } | InjectedFault::FailFirst { .. } => {}
+        }
+    }
+    response
+}
+
+fn json_response(status_code: u16, body: serde_json::Value) -> Response {
+    (
+        StatusCode::from_u16(status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        Json(body),
+    )
+        .into_response()
+}
+
+fn mock_response(mock: MockResponse) -> Response {
+    let mut response = mock.body.into_response();
+    *response.status_mut() =
+        StatusCode::from_u16(mock.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let response_headers = response.headers_mut();
+    response_headers.remove(header::CONTENT_TYPE);
+    if let Some(content_type) = mock.content_type {
+        if let Ok(value) = HeaderValue::from_str(&content_type) {
+            response_headers.insert(header::CONTENT_TYPE, value);
+        }
+    }
+    for (name, value) in mock.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            response_headers.insert(name, value);
+        }
+    }
+    response
+}
+
+/// Mantém status e headers, mas o corpo para na metade com erro, o que faz o hyper
+/// fechar a conexão sem concluir a resposta.
+async fn drop_midway(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let bytes = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .unwrap_or_default();
+    let half = bytes.slice(..bytes.len() / 2);
+    // A pausa antes do erro deixa o hyper enviar o que já tem antes de fechar.
+    let chunks = stream::once(async move { Ok(half) }).chain(stream::once(async {
+        tokio::task::yield_now().await;
+        Err(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "Injected fault: connection dropped",
+        ))
+    }));
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from_stream(chunks))
+}
+
+async fn health_check() -> &'static str {
+    "Webhook server is running"
+}
+
+/// Servidor HTTP em background servindo o router de um [`WebhookCapture`].
+/// Para quando `stop` é chamado ou quando o valor é descartado.
+pub struct WebhookServer {
+    addr: SocketAddr,
+    handle: tokio::task::JoinHandle<()>,
+    capture: WebhookCapture,
+}
+
+impl WebhookServer {
+    /// Escuta em `0.0.0.0:port`; com `port` 0 o sistema escolhe uma porta livre.
+    pub async fn start(port: u16, capture: &WebhookCapture) -> Result<Self, String> {
+        let addr = format!("0.0.0.0:{}", port);
+        let bound = tokio::net::TcpListener::bind(&addr)
+            .await
+            .map_err(|e| format!("Failed to bind to {}: {}", addr, e))
+            .and_then(|listener| {
+                let addr = listener
+                    .local_addr()
+                    .map_err(|e| format!("Failed to read local address: {}", e))?;
+                Ok((listener, addr))
+            });
+        let (listener, addr) = match bound {
+            Ok(bound) => bound,
+            Err(error) => {
+                capture.emit(WebhookEvent::BindError {
+                    error: error.clone(),
+                });
+                return Err(error);
+            }
+        };
+
+        let app = capture.router();
+        let events = capture.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("Server error: {}", e);
+                events.emit(WebhookEvent::Crashed {
+                    error: e.to_string(),
+                });
+            }
+        });
+
+        let server = WebhookServer {
+            addr,
+            handle,
+            capture: capture.clone(),
+        };
+        capture.emit(WebhookEvent::Started { url: server.url() });
+
+        Ok(server)
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://localhost:{}/webhook", self.port())
+    }
+
+    /// Encerra o servidor; equivale a descartar o valor.
+    pub fn stop(self) {}
+}
+
+impl Drop for WebhookServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+        self.capture.emit(WebhookEvent::Stopped);
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SendWebhookResponse {
+    pub status_code: u16,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub error_reason: Option<String>,
+}
+
+pub async fn send_webhook_request(
+    url: String,
+    method: String,
+    headers: HashMap<String, String>,
+    body: String,
+) -> Result<SendWebhookResponse, String> {
+    let client = reqwest::Client::new();
+
+    let mut request_builder = match method.to_uppercase().as_str() {
+        "GET" => client.get(&url),
+        "POST" => client.post(&url),
+        "PUT" => client.put(&url),
+        "DELETE" => client.delete(&url),
+        "PATCH" => client.patch(&url),
+        _ => return Err(format!("Unsupported HTTP method: {}", method)),
+    };
+
+    for (key, value) in &headers {
+        request_builder = request_builder.header(key.as_str(), value.as_str());
+    }
+
+    if !body.is_empty() && method.to_uppercase() != "GET" {
+        request_builder = request_builder
+            .header("content-type", "application/json")
+            .body(body);
+    }
+
+    match request_builder.send().await {
+        Ok(response) => {
+            let status_code = response.status().as_u16();
+            let resp_headers: HashMap<String, String> = response
+                .headers()
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+                .collect();
+            let resp_body = response.text().await.unwrap_or_default();
+
+            let error_reason = if status_code >= 400 {
+                Some(format!(
+                    "HTTP {}: {}",
+                    status_code,
+                    resp_body.chars().take(200).collect::<String>()
+                ))
+            } else {
+                None
+            };
+
+            Ok(SendWebhookResponse {
+                status_code,
+                headers: resp_headers,
+                body: resp_body,
+                error_reason,
+            })
+        }
+        Err(e) => Err(format!("Request failed: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Captura com um listener que guarda todos os eventos emitidos.
+    fn capture_with_log() -> (WebhookCapture, Arc<Mutex<Vec<WebhookEvent>>>) {
+        let capture = WebhookCapture::new();
+        let events: Arc<Mutex<Vec<WebhookEvent>>> = Arc::default();
+        let log = events.clone();
+        capture.set_listener(move |event| log.lock().unwrap().push(event.clone()));
+        (capture, events)
+    }
+
+    fn captured(events: &Mutex<Vec<WebhookEvent>>) -> Vec<WebhookRequest> {
+        events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                WebhookEvent::Request(request) => Some(*request.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn captures_any_method_and_path() {
+        let (capture, events) = capture_with_log();
+        let server = WebhookServer::start(0, &capture).await.unwrap();
+        let base = format!("http://127.0.0.1:{}", server.port());
+        let client = reqwest::Client::new();
+
+        let health = client.get(format!("{}/health", base)).send().await.unwrap();
+        assert_eq!(health.text().await.unwrap(), "Webhook server is running");
+        let response = client
+            .put(format!("{}/hooks/stripe/v2?a=1&b=x%20y&a=2", base))
+            .body("oi")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        client.delete(format!("{}/", base)).send().await.unwrap();
+        client
+            .post(format!("{}/webhook", base))
+            .body(vec![0xff, b'A'])
+            .send()
+            .await
+            .unwrap();
+
+        let requests = captured(&events);
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[0].path, "/hooks/stripe/v2");
+        assert_eq!(requests[0].query["a"], "2");
+        assert_eq!(requests[0].query["b"], "x y");
+        assert_eq!(requests[0].route.as_deref(), Some("/*path"));
+        assert_eq!(requests[0].body, "oi");
+        assert_eq!(requests[1].method, "DELETE");
+        assert_eq!(requests[1].route.as_deref(), Some("/"));
+        assert_eq!(requests[2].body, "\u{fffd}A");
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_credentials_with_401() {
+        let (capture, events) = capture_with_log();
+        capture.set_auth("api-key".to_string(), "secret".to_string());
+        let server = WebhookServer::start(0, &capture).await.unwrap();
+        let url = format!("http://127.0.0.1:{}/hooks", server.port());
+        let client = reqwest::Client::new();
+
+        let denied = client.patch(&url).send().await.unwrap();
+        assert_eq!(denied.status(), 401);
+        let allowed = client
+            .patch(&url)
+            .header("x-api-key", "secret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(allowed.status(), 200);
+
+        let requests = captured(&events);
+        assert_eq!(requests[0].status_code, 401);
+        assert!(requests[0].error_reason.is_some());
+        assert_eq!(requests[1].status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn emits_lifecycle_and_request_events() {
+        let (capture, events) = capture_with_log();
+        let server = WebhookServer::start(0, &capture).await.unwrap();
+        let port = server.port();
+        assert!(WebhookServer::start(port, &capture).await.is_err());
+        reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{}/a?x=1", port))
+            .body("hi")
+            .send()
+            .await
+            .unwrap();
+        server.stop();
+
+        let events: Vec<String> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap())
+            .collect();
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events[0],
+            format!(
+                r#"{{"type":"started","url":"http://localhost:{}/webhook"}}"#,
+                port
+            )
+        );
+        assert!(events[1].starts_with(r#"{"type":"bindError","error":"Failed to bind"#));
+        assert!(events[2].starts_with(r#"{"type":"request","id":"#));
+        assert!(events[2].contains(r#""body":"hi""#));
+        assert_eq!(events[3], r#"{"type":"stopped"}"#);
+    }
+
+    #[tokio::test]
+    async fn injects_faults_before_answering() {
+        let (capture, events) = capture_with_log();
+        capture.set_faults(FaultConfig {
+            fail_first: Some(1),
+            ..Default::default()
+        });
+        let server = WebhookServer::start(0, &capture).await.unwrap();
+        let url = format!("http://127.0.0.1:{}/retry", server.port());
+        let client = reqwest::Client::new();
+
+        assert_eq!(client.post(&url).send().await.unwrap().status(), 503);
+        assert_eq!(client.post(&url).send().await.unwrap().status(), 200);
+        capture.reset_fault_counters();
+        assert_eq!(client.post(&url).send().await.unwrap().status(), 503);
+
+        capture.set_faults(FaultConfig {
+            drop_connection: true,
+            ..Default::default()
+        });
+        let dropped = client.post(&url).send().await.unwrap();
+        assert_eq!(dropped.status(), 200);
+        assert!(dropped.bytes().await.is_err());
+
+        let requests = captured(&events);
+        assert_eq!(
+            requests[0].faults,
+            [InjectedFault::FailFirst {
+                attempt: 1,
+                of: 1,
+                status_code: 503
+            }]
+        );
+        assert_eq!(requests[0].status_code, 503);
+        assert!(requests[1].faults.is_empty());
+        assert_eq!(requests[3].faults, [InjectedFault::DropConnection]);
+    }
+}
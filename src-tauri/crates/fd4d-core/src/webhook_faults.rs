@@ -0,0 +1,324 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const DEFAULT_ERROR_STATUS: u16 = 503;
+/// Até quando `hang` segura a resposta; passa de qualquer timeout razoável de cliente
+/// sem deixar conexões presas para sempre depois que o servidor para.
+pub const MAX_HANG: Duration = Duration::from_secs(300);
+
+/// Falhas simuladas no receptor, globais ou por regra de resposta.
+/// Campos vazios não injetam nada.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FaultConfig {
+    /// Atraso fixo ou, com `delay_max_ms`, o mínimo de um atraso sorteado.
+    pub delay_ms: Option<u64>,
+    pub delay_max_ms: Option<u64>,
+    /// Probabilidade, de 0 a 1, de responder com `error_status`.
+    pub error_rate: Option<f64>,
+    /// Status das falhas injetadas; padrão 503.
+    pub error_status: Option<u16>,
+    /// Não responde: a conexão fica aberta até o cliente desistir (ou até [`MAX_HANG`]).
+    #[serde(default)]
+    pub hang: bool,
+    /// Envia os headers e metade do corpo e derruba a conexão.
+    #[serde(default)]
+    pub drop_connection: bool,
+    /// Falha as primeiras N requisições com `error_status` e depois segue normalmente.
+    pub fail_first: Option<u32>,
+}
+
+/// Falha aplicada a uma requisição, registrada em `WebhookRequest::faults`.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum InjectedFault {
+    Delay {
+        ms: u64,
+    },
+    /// Sorteada por `error_rate`.
+    Error {
+        status_code: u16,
+    },
+    /// Tentativa `attempt` de `of` em `fail_first`.
+    FailFirst {
+        attempt: u32,
+        of: u32,
+        status_code: u16,
+    },
+    Hang,
+    DropConnection,
+}
+
+impl InjectedFault {
+    /// Status forçado pela falha, se ela substitui a resposta.
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            InjectedFault::Error { status_code } | InjectedFault::FailFirst { status_code, .. } => {
+                Some(*status_code)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FaultConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self
+            .error_rate
+            .is_some_and(|rate| !(0.0..=1.0).contains(&rate))
+        {
+            return Err("Error rate must be between 0 and 1".to_string());
+        }
+        if let Some(status) = self.error_status {
+            if !(100..=599).contains(&status) {
+                return Err(format!("Invalid status code: {}", status));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.delay_ms, self.delay_max_ms) {
+            if max < min {
+                return Err("Maximum delay must not be less than the minimum".to_string());
+            }
+        }
+        if self.hang && self.drop_connection {
+            return Err("Choose either hang or drop connection".to_string());
+        }
+        Ok(())
+    }
+
+    /// Sorteia as falhas da `attempt`-ésima requisição (a partir de 1) desta configuração,
+    /// na ordem em que devem ser aplicadas.
+    pub fn plan(&self, attempt: u32, rng: &mut impl Rng) -> Vec<InjectedFault> {
+        let mut faults = Vec::new();
+        let status_code = self.error_status.unwrap_or(DEFAULT_ERROR_STATUS);
+
+        match self.fail_first {
+            Some(of) if attempt <= of => faults.push(InjectedFault::FailFirst {
+                attempt,
+                of,
+                status_code,
+            }),
+            _ => {
+                if self
+                    .error_rate
+                    .is_some_and(|rate| rng.random_bool(rate.clamp(0.0, 1.0)))
+                {
+                    faults.push(InjectedFault::Error { status_code });
+                }
+            }
+        }
+
+        let delay = match (self.delay_ms, self.delay_max_ms) {
+            (Some(min), Some(max)) if max > min => Some(rng.random_range(min..=max)),
+            (None, Some(max)) => Some(rng.random_range(0..=max)),
+            (delay, _) => delay,
+        };
+        if let Some(ms) = delay.filter(|ms| *ms > 0) {
+            faults.push(InjectedFault::Delay { ms });
+        }
+
+        if self.hang {
+            faults.push(InjectedFault::Hang);
+        } else if self.drop_connection {
+            faults.push(InjectedFault::DropConnection);
+        }
+
+        faults
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn rng() -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(42)
+    }
+
+    #[test]
+    fn validates_the_configuration() {
+        assert!(FaultConfig::default().validate().is_ok());
+        let valid = FaultConfig {
+            delay_ms: Some(10),
+            delay_max_ms: Some(10),
+            error_rate: Some(1.0),
+            error_status: Some(500),
+            hang: true,
+            ..Default::default()
+        };
+        assert!(valid.validate().is_ok());
+
+        for invalid in [
+            FaultConfig {
+                error_rate: Some(-0.1),
+                ..Default::default()
+            },
+            FaultConfig {
+                error_rate: Some(1.5),
+                ..Default::default()
+            },
+            FaultConfig {
+                error_status: Some(99),
+                ..Default::default()
+            },
+            FaultConfig {
+                delay_ms: Some(20),
+                delay_max_ms: Some(10),
+                ..Default::default()
+            },
+            FaultConfig {
+                hang: true,
+                drop_connection: true,
+                ..Default::default()
+            },
+        ] {
+            assert!(invalid.validate().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn empty_configuration_injects_nothing() {
+        let mut rng = rng();
+        for attempt in 1..=20 {
+            assert!(FaultConfig::default().plan(attempt, &mut rng).is_empty());
+        }
+    }
+
+    #[test]
+    fn fails_the_first_attempts_then_recovers() {
+        let faults = FaultConfig {
+            fail_first: Some(2),
+            error_status: Some(500),
+            ..Default::default()
+        };
+        let mut rng = rng();
+        assert_eq!(
+            faults.plan(1, &mut rng),
+            [InjectedFault::FailFirst {
+                attempt: 1,
+                of: 2,
+                status_code: 500
+            }]
+        );
+        assert_eq!(faults.plan(2, &mut rng)[0].status_code(), Some(500));
+        assert!(faults.plan(3, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn error_rate_zero_and_one_are_deterministic() {
+        let mut rng = rng();
+        let never = FaultConfig {
+            error_rate: Some(0.0),
+            ..Default::default()
+        };
+        let always = FaultConfig {
+            error_rate: Some(1.0),
+            ..Default::default()
+        };
+        for attempt in 1..=50 {
+            assert!(never.plan(attempt, &mut rng).is_empty());
+            assert_eq!(
+                always.plan(attempt, &mut rng),
+                [InjectedFault::Error {
+                    status_code: DEFAULT_ERROR_STATUS
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn delays_stay_within_the_range() {
+        let mut rng = rng();
+        let fixed = FaultConfig {
+            delay_ms: Some(150),
+            ..Default::default()
+        };
+        assert_eq!(fixed.plan(1, &mut rng), [InjectedFault::Delay { ms: 150 }]);
+        let same_bounds = FaultConfig {
+            delay_ms: Some(150),
+            delay_max_ms: Some(150),
+            ..Default::default()
+        };
+        assert_eq!(
+            same_bounds.plan(1, &mut rng),
+            [InjectedFault::Delay { ms: 150 }]
+        );
+        assert!(FaultConfig {
+            delay_ms: Some(0),
+            ..Default::default()
+        }
+        .plan(1, &mut rng)
+        .is_empty());
+
+        let ranged = FaultConfig {
+            delay_ms: Some(100),
+            delay_max_ms: Some(200),
+            ..Default::default()
+        };
+        let only_max = FaultConfig {
+            delay_max_ms: Some(50),
+            ..Default::default()
+        };
+        for attempt in 1..=100 {
+            match ranged.plan(attempt, &mut rng)[..] {
+                [InjectedFault::Delay { ms }] => assert!((100..=200).contains(&ms)),
+                ref other => panic!("{:?}", other),
+            }
+            match only_max.plan(attempt, &mut rng)[..] {
+                [] => {}
+                [InjectedFault::Delay { ms }] => assert!(ms <= 50),
+                ref other => panic!("{:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn plans_faults_in_application_order() {
+        let mut rng = rng();
+        let faults = FaultConfig {
+            delay_ms: Some(10),
+            error_rate: Some(1.0),
+            drop_connection: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            faults.plan(1, &mut rng),
+            [
+                InjectedFault::Error {
+                    status_code: DEFAULT_ERROR_STATUS
+                },
+                InjectedFault::Delay { ms: 10 },
+                InjectedFault::DropConnection,
+            ]
+        );
+        let hang = FaultConfig {
+            hang: true,
+            ..Default::default()
+        };
+        assert_eq!(hang.plan(1, &mut rng), [InjectedFault::Hang]);
+        assert_eq!(InjectedFault::Hang.status_code(), None);
+    }
+
+    #[test]
+    fn serializes_faults_with_a_type_tag() {
+        let fault = InjectedFault::FailFirst {
+            attempt: 1,
+            of: 3,
+            status_code: 503,
+        };
+        assert_eq!(
+            serde_json::to_string(&fault).unwrap(),
+            r#"{"type":"failFirst","attempt":1,"of":3,"statusCode":503}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&InjectedFault::DropConnection).unwrap(),
+            r#"{"type":"dropConnection"}"#
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use crate::webhook::WebhookRequest;
+use crate::webhook_faults::FaultConfig;
+use axum::http::{HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Exige o header `name` (sem diferenciar maiúsculas); com `value`, também o valor exato.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderMatch {
+    pub name: String,
+    pub value: Option<String>,
+}
+
+/// Exige o campo `field` do corpo JSON, em notação de pontos (`data.status`, `items.0.id`);
+/// com `value`, também o valor (strings comparadas sem aspas).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyFieldMatch {
+    pub field: String,
+    pub value: Option<String>,
+}
+
+/// Resposta simulada para as requisições que atendem a todos os critérios preenchidos.
+/// `body` e os valores de `headers` aceitam placeholders como `{{request.id}}`,
+/// `{{query.page}}`, `{{headers.x-signature}}`, `{{body}}` e `{{body.event}}`.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseRule {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    pub method: Option<String>,
+    /// Caminho exato ou com `*`, que casa com qualquer trecho (ex.: `/hooks/*`).
+    pub path: Option<String>,
+    pub header: Option<HeaderMatch>,
+    pub body_field: Option<BodyFieldMatch>,
+    pub status_code: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    /// Sem valor, usa `application/json` quando há corpo.
+    pub content_type: Option<String>,
+    /// Falhas desta regra; sem elas, valem as globais.
+    #[serde(default)]
+    pub faults: Option<FaultConfig>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// Resposta de uma regra já com os placeholders resolvidos.
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    pub status_code: u16,
+    pub content_type: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+/// Casa `pattern` com `path`; cada `*` aceita qualquer sequência, inclusive com `/`.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Navega em `value` pela notação de pontos; segmentos numéricos indexam arrays.
+fn json_field<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+    field
+        .split('.')
+        .try_fold(value, |current, key| match current {
+            Value::Object(map) => map.get(key),
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+fn json_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+impl ResponseRule {
+    /// Rejeita status fora de 100–599, nomes ou valores de header inválidos e
+    /// configurações de falha inconsistentes.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(faults) = &self.faults {
+            faults.validate()?;
+        }
+        if !(100..=599).contains(&self.status_code) {
+            return Err(format!("Invalid status code: {}", self.status_code));
+        }
+        if let Some(header) = &self.header {
+            HeaderName::from_bytes(header.name.as_bytes())
+                .map_err(|_| format!("Invalid header name: {}", header.name))?;
+        }
+        if self
+            .body_field
+            .as_ref()
+            .is_some_and(|body_field| body_field.field.trim().is_empty())
+        {
+            return Err("Body field must not be empty".to_string());
+        }
+        for (name, value) in &self.headers {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("Invalid header name: {}", name))?;
+            HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid value for header {}", name))?;
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, request: &WebhookRequest, json_body: Option<&Value>) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if self
+            .method
+            .as_ref()
+            .is_some_and(|method| !method.eq_ignore_ascii_case(&request.method))
+        {
+            return false;
+        }
+        if self
+            .path
+            .as_ref()
+            .is_some_and(|pattern| !path_matches(pattern, &request.path))
+        {
+            return false;
+        }
+        if let Some(header) = &self.header {
+            let found = request
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&header.name));
+            match (found, &header.value) {
+                (None, _) => return false,
+                (Some((_, actual)), Some(expected)) if actual != expected => return false,
+                _ => {}
+            }
+        }
+        if let Some(body_field) = &self.body_field {
+            let found = json_body.and_then(|body| json_field(body, &body_field.field));
+            match (found, &body_field.value) {
+                (None, _) => return false,
+                (Some(actual), Some(expected)) if json_to_text(actual) != *expected => {
+                    return false
+                }
+                _ => {}
+            }
+        }
+        true
+    }
+
+    /// Monta a resposta para `request`. Em corpos JSON, os valores inseridos são escapados
+    /// para caber dentro de uma string (`"{{body.name}}"`).
+    pub fn render(&self, request: &WebhookRequest, json_body: Option<&Value>) -> MockResponse {
+        let content_type = self
+            .content_type
+            .clone()
+            .filter(|content_type| !content_type.trim().is_empty())
+            .or_else(|| (!self.body.is_empty()).then(|| "application/json".to_string()));
+        let escape_json = content_type
+            .as_deref()
+            .is_some_and(|content_type| content_type.contains("json"));
+
+        MockResponse {
+            status_code: self.status_code,
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.clone(),
+                        render_template(value, request, json_body, false),
+                    )
+                })
+                .collect(),
+            body: render_template(&self.body, request, json_body, escape_json),
+            content_type,
+        }
+    }
+}
+
+fn resolve_placeholder(key: &str, request: &WebhookRequest, json_body: Option<&Value>) -> String {
+    let (scope, field) = key.split_once('.').unwrap_or((key, ""));
+    match (scope, field) {
+        ("request", "id") => request.id.clone(),
+        ("request", "method") => request.method.clone(),
+        ("request", "path") => request.path.clone(),
+        ("request", "timestamp") => request.timestamp.clone(),
+        ("query", name) => request.query.get(name).cloned().unwrap_or_default(),
+        ("headers", name) => request
+            .headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default(),
+        ("body", "") => request.body.clone(),
+        ("body", field) => json_body
+            .and_then(|body| json_field(body, field))
+            .map(json_to_text)
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Troca cada `{{chave}}` pelo valor correspondente da requisição; chaves desconhecidas
+/// viram texto vazio e um `{{` sem fechamento é mantido como está.
+pub fn render_template(
+    template: &str,
+    request: &WebhookRequest,
+    json_body: Option<&Value>,
+    escape_json: bool,
+) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let key = rest[start + 2..start + 2 + end].trim();
+        let value = resolve_placeholder(key, request, json_body);
+        if escape_json {
+            let quoted = Value::String(value).to_string();
+            output.push_str(&quoted[1..quoted.len() - 1]);
+        } else {
+            output.push_str(&value);
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request() -> WebhookRequest {
+        WebhookRequest {
+            id: "req-1".to_string(),
+            timestamp: "2024-01-02 03:04:05".to_string(),
+            method: "POST".to_string(),
+            path: "/hooks/stripe/v2".to_string(),
+            query: HashMap::from([("page".to_string(), "3".to_string())]),
+            route: Some("/*path".to_string()),
+            headers: HashMap::from([("X-Signature".to_string(), "abc".to_string())]),
+            body: r#"{"event":"paid","data":{"items":[{"id":7}],"note":"a \"b\""}}"#.to_string(),
+            status_code: 200,
+            error_reason: None,
+            matched_rule: None,
+            faults: Vec::new(),
+        }
+    }
+
+    fn minimal_rule() -> ResponseRule {
+        serde_json::from_value(json!({ "statusCode": 201 })).unwrap()
+    }
+
+    #[test]
+    fn matches_paths_with_wildcards() {
+        assert!(path_matches("/hooks", "/hooks"));
+        assert!(!path_matches("/hooks", "/hooks/"));
+        assert!(path_matches("/hooks/*", "/hooks/stripe/v2"));
+        assert!(path_matches("*/v2", "/hooks/stripe/v2"));
+        assert!(path_matches("/hooks/*/v2", "/hooks/stripe/v2"));
+        assert!(path_matches("/*/*/v2", "/hooks/stripe/v2"));
+        assert!(path_matches("*", ""));
+        assert!(!path_matches("/hooks/*/v3", "/hooks/stripe/v2"));
+        assert!(!path_matches("/a*a", "/a"));
+    }
+
+    #[test]
+    fn reads_nested_json_fields() {
+        let body = json!({ "data": { "items": [{ "id": 7 }], "ok": true } });
+        assert_eq!(json_field(&body, "data.items.0.id"), Some(&json!(7)));
+        assert_eq!(json_field(&body, "data.ok"), Some(&json!(true)));
+        assert_eq!(json_field(&body, "data.items.1"), None);
+        assert_eq!(json_field(&body, "data.items.first"), None);
+        assert_eq!(json_field(&body, "data.ok.value"), None);
+    }
+
+    #[test]
+    fn matches_only_when_every_criterion_holds() {
+        let request = request();
+        let body = serde_json::from_str::<Value>(&request.body).ok();
+        let matches = |value: Value| {
+            let rule: ResponseRule = serde_json::from_value(value).unwrap();
+            rule.matches(&request, body.as_ref())
+        };
+
+        assert!(minimal_rule().enabled);
+        assert!(minimal_rule().matches(&request, body.as_ref()));
+        assert!(!matches(json!({ "statusCode": 201, "enabled": false })));
+        assert!(matches(json!({ "statusCode": 201, "method": "post" })));
+        assert!(!matches(json!({ "statusCode": 201, "method": "GET" })));
+        assert!(matches(json!({ "statusCode": 201, "path": "/hooks/*" })));
+        assert!(!matches(json!({ "statusCode": 201, "path": "/other/*" })));
+        assert!(matches(
+            json!({ "statusCode": 201, "header": { "name": "x-signature" } })
+        ));
+        assert!(!matches(
+            json!({ "statusCode": 201, "header": { "name": "x-signature", "value": "xyz" } })
+        ));
+        assert!(matches(
+            json!({ "statusCode": 201, "bodyField": { "field": "event", "value": "paid" } })
+        ));
+        assert!(matches(
+            json!({ "statusCode": 201, "bodyField": { "field": "data.items.0.id", "value": "7" } })
+        ));
+        assert!(!matches(
+            json!({ "statusCode": 201, "bodyField": { "field": "event", "value": "failed" } })
+        ));
+        let with_body_field: ResponseRule =
+            serde_json::from_value(json!({ "statusCode": 201, "bodyField": { "field": "event" } }))
+                .unwrap();
+        assert!(!with_body_field.matches(&request, None));
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let request = request();
+        let body = serde_json::from_str::<Value>(&request.body).ok();
+        let render = |template: &str, escape_json: bool| {
+            render_template(template, &request, body.as_ref(), escape_json)
+        };
+
+        assert_eq!(
+            render(
+                "{{request.id}} {{ request.method }} {{request.path}} {{query.page}}",
+                false
+            ),
+            "req-1 POST /hooks/stripe/v2 3"
+        );
+        assert_eq!(render("{{headers.x-signature}}", false), "abc");
+        assert_eq!(render("{{body.data.items.0.id}}", false), "7");
+        assert_eq!(render("[{{unknown}}][{{query.none}}]", false), "[][]");
+        assert_eq!(render("{{body.data.note}}", false), r#"a "b""#);
+        assert_eq!(render("{{body.data.note}}", true), r#"a \"b\""#);
+        assert_eq!(render("{{body}}", false), request.body);
+        assert_eq!(render("open {{request.id", false), "open {{request.id");
+    }
+
+    #[test]
+    fn renders_the_response() {
+        let request = request();
+        let body = serde_json::from_str::<Value>(&request.body).ok();
+        let rule: ResponseRule = serde_json::from_value(json!({
+            "statusCode": 202,
+            "headers": { "x-request": "{{request.id}}" },
+            "body": r#"{"received":"{{body.data.note}}"}"#,
+        }))
+        .unwrap();
+
+        let response = rule.render(&request, body.as_ref());
+        assert_eq!(response.status_code, 202);
+        assert_eq!(response.content_type.as_deref(), Some("application/json"));
+        assert_eq!(
+            response.headers,
+            [("x-request".to_string(), "req-1".to_string())]
+        );
+        assert_eq!(response.body, r#"{"received":"a \"b\""}"#);
+        assert!(serde_json::from_str::<Value>(&response.body).is_ok());
+
+        let plain = minimal_rule().render(&request, body.as_ref());
+        assert!(plain.content_type.is_none());
+        assert!(plain.body.is_empty());
+    }
+
+    #[test]
+    fn validates_rules() {
+        assert!(minimal_rule().validate().is_ok());
+        for invalid in [
+            json!({ "statusCode": 99 }),
+            json!({ "statusCode": 600 }),
+            json!({ "statusCode": 200, "header": { "name": "bad header" } }),
+            json!({ "statusCode": 200, "bodyField": { "field": " " } }),
+            json!({ "statusCode": 200, "headers": { "x-ok": "line\nbreak" } }),
+            json!({ "statusCode": 200, "headers": { "bad:name": "value" } }),
+            json!({ "statusCode": 200, "faults": { "errorRate": 2.0 } }),
+        ] {
+            let rule: ResponseRule = serde_json::from_value(invalid.clone()).unwrap();
+            assert!(rule.validate().is_err(), "{}", invalid);
+        }
+    }
+}
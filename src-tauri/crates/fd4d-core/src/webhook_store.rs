@@ -0,0 +1,709 @@
+use crate::webhook::WebhookRequest;
+use crate::webhook_faults::FaultConfig;
+use crate::webhook_rules::ResponseRule;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const REQUESTS_FILE: &str = "requests.ndjson";
+const STORE_FILE: &str = "store.json";
+/// Retenção padrão; `None` em `max_requests` deixa as capturas sem limite de quantidade.
+const DEFAULT_MAX_REQUESTS: usize = 5000;
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+/// Linhas descartadas toleradas no NDJSON antes de reescrevê-lo só com as capturas retidas.
+const COMPACTION_SLACK: usize = 500;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookRetention {
+    pub max_requests: Option<usize>,
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for WebhookRetention {
+    fn default() -> Self {
+        WebhookRetention {
+            max_requests: Some(DEFAULT_MAX_REQUESTS),
+            max_age_days: None,
+        }
+    }
+}
+
+/// Um ciclo de vida do servidor, do `start` ao `stop` (ou ao fechamento do app).
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSession {
+    pub id: String,
+    pub url: String,
+    /// Milissegundos desde a época Unix.
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    /// Recalculado ao abrir o store; o valor gravado em disco é só informativo.
+    #[serde(default)]
+    pub request_count: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredWebhookRequest {
+    pub session_id: Option<String>,
+    /// Milissegundos desde a época Unix; é o campo usado nos filtros de tempo.
+    pub received_at: u64,
+    #[serde(flatten)]
+    pub request: WebhookRequest,
+}
+
+/// Filtros de [`WebhookStore::query`]. Campos ausentes não filtram.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookQuery {
+    pub session_id: Option<String>,
+    /// Trecho procurado, sem diferenciar maiúsculas, no caminho, query, headers e corpo.
+    pub search: Option<String>,
+    pub method: Option<String>,
+    /// Trecho do caminho.
+    pub path: Option<String>,
+    pub status_min: Option<u16>,
+    pub status_max: Option<u16>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPage {
+    pub items: Vec<StoredWebhookRequest>,
+    /// Total de capturas que atendem aos filtros, sem a paginação.
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct StoreFile {
+    #[serde(default)]
+    retention: WebhookRetention,
+    #[serde(default)]
+    sessions: Vec<WebhookSession>,
+    /// Respostas simuladas, na ordem em que são avaliadas.
+    #[serde(default)]
+    rules: Vec<ResponseRule>,
+    /// Falhas globais, para requisições sem regra com falhas próprias.
+    #[serde(default)]
+    faults: FaultConfig,
+}
+
+/// Capturas gravadas em disco: um NDJSON só de acréscimo com as requisições e um JSON com
+/// as sessões, a retenção, as regras de resposta e as falhas simuladas. Tudo fica indexado
+/// em memória para as consultas.
+pub struct WebhookStore {
+    dir: PathBuf,
+    data: StoreFile,
+    /// Da mais antiga para a mais recente, na ordem do arquivo.
+    requests: Vec<StoredWebhookRequest>,
+    current_session: Option<String>,
+    /// Linhas no NDJSON, incluindo as que a retenção já descartou da memória.
+    lines: usize,
+    file: File,
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+impl StoredWebhookRequest {
+    fn matches(&self, query: &WebhookQuery, search: Option<&str>) -> bool {
+        let request = &self.request;
+
+        if query
+            .session_id
+            .as_ref()
+            .is_some_and(|id| self.session_id.as_ref() != Some(id))
+            || query
+                .method
+                .as_ref()
+                .is_some_and(|method| !request.method.eq_ignore_ascii_case(method))
+            || query
+                .path
+                .as_ref()
+                .is_some_and(|path| !request.path.to_lowercase().contains(&path.to_lowercase()))
+            || query
+                .status_min
+                .is_some_and(|min| request.status_code < min)
+            || query
+                .status_max
+                .is_some_and(|max| request.status_code > max)
+            || query.since.is_some_and(|since| self.received_at < since)
+            || query.until.is_some_and(|until| self.received_at > until)
+        {
+            return false;
+        }
+
+        let Some(search) = search else {
+            return true;
+        };
+        request.path.to_lowercase().contains(search)
+            || request.body.to_lowercase().contains(search)
+            || request
+                .headers
+                .iter()
+                .chain(request.query.iter())
+                .any(|(key, value)| {
+                    key.to_lowercase().contains(search) || value.to_lowercase().contains(search)
+                })
+    }
+}
+
+impl WebhookStore {
+    /// Abre (ou cria) o store em `dir`. Linhas ilegíveis do NDJSON, como uma escrita
+    /// interrompida, são descartadas; um `store.json` corrompido vira `.bak`.
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        let store_path = dir.join(STORE_FILE);
+        let data = match fs::read_to_string(&store_path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                eprintln!("Invalid webhook store ({}), starting empty", e);
+                let _ = fs::rename(&store_path, store_path.with_extension("json.bak"));
+                StoreFile::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => StoreFile::default(),
+            Err(e) => return Err(format!("Failed to read {}: {}", store_path.display(), e)),
+        };
+
+        let requests_path = dir.join(REQUESTS_FILE);
+        let mut requests: Vec<StoredWebhookRequest> = Vec::new();
+        let mut lines = 0;
+        let mut damaged = false;
+        match File::open(&requests_path) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line.map_err(|e| {
+                        format!("Failed to read {}: {}", requests_path.display(), e)
+                    })?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    lines += 1;
+                    match serde_json::from_str(&line) {
+                        Ok(request) => requests.push(request),
+                        Err(e) => {
+                            eprintln!("Skipping invalid webhook capture: {}", e);
+                            damaged = true;
+                        }
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Failed to read {}: {}", requests_path.display(), e)),
+        }
+        requests.sort_by_key(|request| request.received_at);
+
+        let file = Self::open_for_append(&requests_path)?;
+        let mut store = WebhookStore {
+            dir: dir.to_path_buf(),
+            data,
+            requests,
+            current_session: None,
+            lines,
+            file,
+        };
+
+        for session in &mut store.data.sessions {
+            session.request_count = store
+                .requests
+                .iter()
+                .filter(|request| request.session_id.as_ref() == Some(&session.id))
+                .count();
+            // Sessões abertas quando o app foi fechado terminam na última captura.
+            if session.ended_at.is_none() {
+                let last = store
+                    .requests
+                    .iter()
+                    .filter(|request| request.session_id.as_ref() == Some(&session.id))
+                    .map(|request| request.received_at)
+                    .max();
+                session.ended_at = Some(last.unwrap_or(session.started_at));
+            }
+        }
+
+        // Sem compactar, o próximo acréscimo se juntaria a uma linha truncada.
+        if damaged {
+            store.compact()?;
+        }
+        store.apply_retention(now_ms())?;
+        store.save()?;
+        Ok(store)
+    }
+
+    fn open_for_append(path: &Path) -> Result<File, String> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+    }
+
+    fn requests_path(&self) -> PathBuf {
+        self.dir.join(REQUESTS_FILE)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = self.dir.join(STORE_FILE);
+        let temporary = path.with_extension("json.tmp");
+        let content = serde_json::to_string_pretty(&self.data)
+            .map_err(|e| format!("Failed to serialize webhook store: {}", e))?;
+        fs::write(&temporary, content)
+            .and_then(|_| fs::rename(&temporary, &path))
+            .map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+    }
+
+    /// Reescreve o NDJSON só com as capturas retidas.
+    fn compact(&mut self) -> Result<(), String> {
+        let path = self.requests_path();
+        let temporary = path.with_extension("ndjson.tmp");
+        let io_err = |e: std::io::Error| format!("Failed to write {}: {}", temporary.display(), e);
+
+        let mut writer = BufWriter::new(File::create(&temporary).map_err(io_err)?);
+        for request in &self.requests {
+            serde_json::to_writer(&mut writer, request)
+                .map_err(|e| format!("Failed to serialize webhook capture: {}", e))?;
+            writeln!(writer).map_err(io_err)?;
+        }
+        writer.flush().map_err(io_err)?;
+        drop(writer);
+
+        fs::rename(&temporary, &path)
+            .map_err(|e| format!("Failed to save {}: {}", path.display(), e))?;
+        self.file = Self::open_for_append(&path)?;
+        self.lines = self.requests.len();
+        Ok(())
+    }
+
+    /// Descarta as capturas fora da retenção e as sessões encerradas que ficaram vazias
+    /// antes da captura mais antiga mantida. Retorna se alguma sessão foi removida.
+    fn apply_retention(&mut self, now: u64) -> Result<bool, String> {
+        let retention = &self.data.retention;
+        let mut excess = match retention.max_age_days {
+            Some(days) => {
+                let cutoff = now.saturating_sub(u64::from(days) * DAY_MS);
+                self.requests
+                    .partition_point(|request| request.received_at < cutoff)
+            }
+            None => 0,
+        };
+        if let Some(max) = retention.max_requests {
+            excess = excess.max(self.requests.len().saturating_sub(max));
+        }
+
+        for request in self.requests.drain(..excess) {
+            if let Some(session) = self
+                .data
+                .sessions
+                .iter_mut()
+                .find(|session| request.session_id.as_ref() == Some(&session.id))
+            {
+                session.request_count = session.request_count.saturating_sub(1);
+            }
+        }
+
+        let oldest = self
+            .requests
+            .first()
+            .map_or(now, |request| request.received_at);
+        let sessions = self.data.sessions.len();
+        self.data.sessions.retain(|session| {
+            session.request_count > 0 || session.ended_at.is_none_or(|ended| ended >= oldest)
+        });
+
+        if self.lines > self.requests.len() + COMPACTION_SLACK {
+            self.compact()?;
+        }
+        Ok(self.data.sessions.len() != sessions)
+    }
+
+    /// Abre uma sessão nova, encerrando a anterior se ainda estiver aberta.
+    pub fn start_session(&mut self, url: &str) -> Result<String, String> {
+        self.close_current(now_ms());
+        let id = uuid::Uuid::new_v4().to_string();
+        self.data.sessions.push(WebhookSession {
+            id: id.clone(),
+            url: url.to_string(),
+            started_at: now_ms(),
+            ended_at: None,
+            request_count: 0,
+        });
+        self.current_session = Some(id.clone());
+        self.save()?;
+        Ok(id)
+    }
+
+    /// Encerra a sessão atual; sem sessão aberta, não faz nada.
+    pub fn end_session(&mut self) -> Result<(), String> {
+        if self.close_current(now_ms()) {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn close_current(&mut self, now: u64) -> bool {
+        let Some(id) = self.current_session.take() else {
+            return false;
+        };
+        if let Some(session) = self
+            .data
+            .sessions
+            .iter_mut()
+            .find(|session| session.id == id)
+        {
+            session.ended_at = Some(now);
+        }
+        true
+    }
+
+    /// Grava uma captura na sessão atual (ou sem sessão) e aplica a retenção.
+    pub fn append(&mut self, request: WebhookRequest) -> Result<(), String> {
+        let now = now_ms();
+        let stored = StoredWebhookRequest {
+            session_id: self.current_session.clone(),
+            received_at: now,
+            request,
+        };
+
+        let mut line = serde_json::to_string(&stored)
+            .map_err(|e| format!("Failed to serialize webhook capture: {}", e))?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", self.requests_path().display(), e))?;
+        self.lines += 1;
+
+        if let Some(session) = self
+            .data
+            .sessions
+            .iter_mut()
+            .find(|session| stored.session_id.as_ref() == Some(&session.id))
+        {
+            session.request_count += 1;
+        }
+        self.requests.push(stored);
+
+        if self.apply_retention(now)? {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Capturas que atendem a `query`, da mais recente para a mais antiga.
+    pub fn query(&self, query: &WebhookQuery) -> WebhookPage {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+        let search = query
+            .search
+            .as_deref()
+            .map(str::trim)
+            .filter(|search| !search.is_empty())
+            .map(str::to_lowercase);
+
+        let matching: Vec<&StoredWebhookRequest> = self
+            .requests
+            .iter()
+            .rev()
+            .filter(|request| request.matches(query, search.as_deref()))
+            .collect();
+
+        WebhookPage {
+            items: matching
+                .iter()
+                .skip(query.offset)
+                .take(limit)
+                .map(|request| (*request).clone())
+                .collect(),
+            total: matching.len(),
+            offset: query.offset,
+            limit,
+        }
+    }
+
+    /// Sessões da mais recente para a mais antiga.
+    pub fn sessions(&self) -> Vec<WebhookSession> {
+        self.data.sessions.iter().rev().cloned().collect()
+    }
+
+    pub fn current_session(&self) -> Option<&str> {
+        self.current_session.as_deref()
+    }
+
+    pub fn retention(&self) -> WebhookRetention {
+        self.data.retention.clone()
+    }
+
+    pub fn set_retention(&mut self, retention: WebhookRetention) -> Result<(), String> {
+        if retention.max_requests == Some(0) || retention.max_age_days == Some(0) {
+            return Err("Retention must keep at least 1 request and 1 day".to_string());
+        }
+        self.data.retention = retention;
+        self.apply_retention(now_ms())?;
+        self.save()
+    }
+
+    pub fn rules(&self) -> Vec<ResponseRule> {
+        self.data.rules.clone()
+    }
+
+    /// Substitui todas as regras, na ordem dada; regras sem id ganham um novo.
+    pub fn set_rules(&mut self, mut rules: Vec<ResponseRule>) -> Result<(), String> {
+        for rule in &mut rules {
+            rule.validate()?;
+            if rule.id.is_empty() {
+                rule.id = uuid::Uuid::new_v4().to_string();
+            }
+        }
+        self.data.rules = rules;
+        self.save()
+    }
+
+    /// Atualiza a regra de mesmo id ou, se não existir (ou sem id), adiciona ao final.
+    pub fn save_rule(&mut self, mut rule: ResponseRule) -> Result<ResponseRule, String> {
+        rule.validate()?;
+        if rule.id.is_empty() {
+            rule.id = uuid::Uuid::new_v4().to_string();
+        }
+        match self.data.rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule.clone(),
+            None => self.data.rules.push(rule.clone()),
+        }
+        self.save()?;
+        Ok(rule)
+    }
+
+    pub fn delete_rule(&mut self, id: &str) -> Result<(), String> {
+        let before = self.data.rules.len();
+        self.data.rules.retain(|rule| rule.id != id);
+        if self.data.rules.len() == before {
+            return Err(format!("Rule not found: {}", id));
+        }
+        self.save()
+    }
+
+    pub fn faults(&self) -> FaultConfig {
+        self.data.faults.clone()
+    }
+
+    pub fn set_faults(&mut self, faults: FaultConfig) -> Result<(), String> {
+        faults.validate()?;
+        self.data.faults = faults;
+        self.save()
+    }
+
+    /// Apaga as capturas de uma sessão (e a sessão, se já encerrada) ou, sem `session_id`,
+    /// todas as capturas e sessões encerradas.
+    pub fn clear(&mut self, session_id: Option<&str>) -> Result<(), String> {
+        let current = self.current_session.clone();
+        self.requests.retain(|request| {
+            session_id.is_some_and(|id| request.session_id.as_deref() != Some(id))
+        });
+        self.data.sessions.retain_mut(|session| {
+            let cleared = session_id.is_none_or(|id| session.id == id);
+            if cleared {
+                session.request_count = 0;
+            }
+            !cleared || current.as_ref() == Some(&session.id)
+        });
+        self.compact()?;
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("fd4d-webhook-store-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn request(method: &str, path: &str, body: &str, status_code: u16) -> WebhookRequest {
+        WebhookRequest {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: String::new(),
+            method: method.to_string(),
+            path: path.to_string(),
+            query: HashMap::new(),
+            route: None,
+            headers: HashMap::from([("x-event".to_string(), "invoice.paid".to_string())]),
+            body: body.to_string(),
+            status_code,
+            error_reason: None,
+            matched_rule: None,
+            faults: Vec::new(),
+        }
+    }
+
+    fn paths(store: &WebhookStore, query: &WebhookQuery) -> Vec<String> {
+        store
+            .query(query)
+            .items
+            .into_iter()
+            .map(|stored| stored.request.path)
+            .collect()
+    }
+
+    #[test]
+    fn persists_sessions_and_captures() {
+        let dir = temp_dir();
+        let mut store = WebhookStore::open(&dir).unwrap();
+        store.append(request("POST", "/orphan", "", 200)).unwrap();
+        let session = store
+            .start_session("http://localhost:3000/webhook")
+            .unwrap();
+        store
+            .append(request("POST", "/a", r#"{"id":1}"#, 200))
+            .unwrap();
+        store.append(request("PUT", "/b", "", 404)).unwrap();
+        drop(store);
+
+        let store = WebhookStore::open(&dir).unwrap();
+        assert!(store.current_session().is_none());
+        let sessions = store.sessions();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, session);
+        assert_eq!(sessions[0].request_count, 2);
+        assert!(sessions[0].ended_at.is_some());
+
+        let all = WebhookQuery::default();
+        assert_eq!(paths(&store, &all), ["/b", "/a", "/orphan"]);
+        let in_session = WebhookQuery {
+            session_id: Some(session),
+            ..Default::default()
+        };
+        assert_eq!(paths(&store, &in_session), ["/b", "/a"]);
+        let errors = WebhookQuery {
+            status_min: Some(400),
+            ..Default::default()
+        };
+        assert_eq!(paths(&store, &errors), ["/b"]);
+        let search = WebhookQuery {
+            search: Some(" INVOICE ".to_string()),
+            method: Some("post".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let page = store.query(&search);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items.len(), 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn applies_retention_by_count_and_age() {
+        let dir = temp_dir();
+        let mut store = WebhookStore::open(&dir).unwrap();
+        for path in ["/1", "/2", "/3", "/4"] {
+            store.append(request("POST", path, "", 200)).unwrap();
+        }
+        assert!(store
+            .set_retention(WebhookRetention {
+                max_requests: Some(0),
+                max_age_days: None,
+            })
+            .is_err());
+        store
+            .set_retention(WebhookRetention {
+                max_requests: Some(2),
+                max_age_days: Some(1),
+            })
+            .unwrap();
+        assert_eq!(paths(&store, &WebhookQuery::default()), ["/4", "/3"]);
+
+        store.apply_retention(now_ms() + 2 * DAY_MS).unwrap();
+        assert!(paths(&store, &WebhookQuery::default()).is_empty());
+        drop(store);
+
+        let store = WebhookStore::open(&dir).unwrap();
+        assert_eq!(store.retention().max_requests, Some(2));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn clears_one_session_or_everything() {
+        let dir = temp_dir();
+        let mut store = WebhookStore::open(&dir).unwrap();
+        let first = store
+            .start_session("http://localhost:3000/webhook")
+            .unwrap();
+        store.append(request("POST", "/first", "", 200)).unwrap();
+        let second = store
+            .start_session("http://localhost:3001/webhook")
+            .unwrap();
+        store.append(request("POST", "/second", "", 200)).unwrap();
+
+        store.clear(Some(&first)).unwrap();
+        assert_eq!(paths(&store, &WebhookQuery::default()), ["/second"]);
+        assert_eq!(store.sessions().len(), 1);
+
+        // A sessão aberta continua recebendo capturas depois de limpa.
+        store.clear(None).unwrap();
+        assert!(paths(&store, &WebhookQuery::default()).is_empty());
+        assert_eq!(store.sessions()[0].id, second);
+        store.append(request("POST", "/third", "", 200)).unwrap();
+        assert_eq!(store.sessions()[0].request_count, 1);
+        drop(store);
+
+        let store = WebhookStore::open(&dir).unwrap();
+        assert_eq!(paths(&store, &WebhookQuery::default()), ["/third"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn reopens_after_a_truncated_line() {
+        let dir = temp_dir();
+        let mut store = WebhookStore::open(&dir).unwrap();
+        store.append(request("POST", "/kept", "", 200)).unwrap();
+        drop(store);
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.join(REQUESTS_FILE))
+            .unwrap();
+        file.write_all(br#"{"sessionId":null,"receivedAt":1,"id":"#)
+            .unwrap();
+        drop(file);
+
+        let mut store = WebhookStore::open(&dir).unwrap();
+        store.append(request("POST", "/after", "", 200)).unwrap();
+        drop(store);
+
+        let store = WebhookStore::open(&dir).unwrap();
+        assert_eq!(paths(&store, &WebhookQuery::default()), ["/after", "/kept"]);
+        let content = fs::read_to_string(dir.join(REQUESTS_FILE)).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn backs_up_a_corrupted_store_file() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(STORE_FILE), "{ not json").unwrap();
+
+        let store = WebhookStore::open(&dir).unwrap();
+        assert!(store.sessions().is_empty());
+        assert!(dir.join("store.json.bak").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
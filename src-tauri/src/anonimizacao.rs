@@ -0,0 +1,40 @@
+use fd4d_core::anonimizacao::{anonimizar_texto, ModoAnonimizacao, ResultadoAnonimizacao};
+use fd4d_core::extracao::ler_texto_ou_arquivo;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// Sal da sessão atual: enquanto não for trocado, o mesmo documento gera sempre o mesmo
+/// hash/pseudônimo. Renovado por `nova_sessao_anonimizacao`.
+static SAL_SESSAO: Lazy<Arc<Mutex<String>>> =
+    Lazy::new(|| Arc::new(Mutex::new(uuid::Uuid::new_v4().to_string())));
+
+/// Anonimiza os CPFs/CNPJs de um texto ou arquivo. Sem `sal`, usa o sal da sessão atual;
+/// com `apenas_validos`, trechos com dígito verificador errado são mantidos.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn anonimizar_documentos(
+    texto: Option<String>,
+    caminho: Option<String>,
+    modo: ModoAnonimizacao,
+    sal: Option<String>,
+    apenas_validos: Option<bool>,
+) -> Result<ResultadoAnonimizacao, String> {
+    let sal = sal.unwrap_or_else(|| SAL_SESSAO.lock().unwrap().clone());
+
+    tokio::task::spawn_blocking(move || {
+        let texto = ler_texto_ou_arquivo(texto, caminho)?;
+        Ok(anonimizar_texto(
+            &texto,
+            modo,
+            &sal,
+            apenas_validos.unwrap_or(false),
+        ))
+    })
+    .await
+    .map_err(|e| format!("Erro na task de anonimização: {}", e))?
+}
+
+/// Troca o sal da sessão: a partir daqui os mesmos documentos recebem hashes/pseudônimos novos.
+#[tauri::command]
+pub fn nova_sessao_anonimizacao() {
+    *SAL_SESSAO.lock().unwrap() = uuid::Uuid::new_v4().to_string();
+}
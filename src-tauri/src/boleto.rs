@@ -0,0 +1,30 @@
+use fd4d_core::boleto::{self, BoletoDecodificado, TipoBoleto, ValidacaoBoleto};
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_boleto(
+    tipo: TipoBoleto,
+    banco: Option<String>,
+    vencimento: Option<String>,
+    valor_centavos: Option<u64>,
+    segmento: Option<u32>,
+    semente: Option<u64>,
+) -> Result<BoletoDecodificado, String> {
+    boleto::gerar_boleto(
+        tipo,
+        banco.as_deref(),
+        vencimento.as_deref(),
+        valor_centavos,
+        segmento,
+        semente,
+    )
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_boleto(boleto_input: &str) -> ValidacaoBoleto {
+    boleto::validar_boleto(boleto_input)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn converter_boleto(boleto_input: &str) -> Result<String, String> {
+    boleto::converter_boleto(boleto_input)
+}
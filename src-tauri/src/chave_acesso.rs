@@ -0,0 +1,11 @@
+use fd4d_core::chave_acesso::{self, ChaveAcesso, OpcoesChaveAcesso};
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_chave_acesso(opcoes: OpcoesChaveAcesso) -> Result<ChaveAcesso, String> {
+    chave_acesso::gerar_chave_acesso(&opcoes)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn decodificar_chave_acesso(chave: &str) -> ChaveAcesso {
+    chave_acesso::decodificar_chave_acesso(chave)
+}
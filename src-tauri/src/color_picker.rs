@@ -1,52 +1,8 @@
-use serde::{Deserialize, Serialize};
+use fd4d_core::color::PixelColor;
 
 #[cfg(any(target_os = "linux", target_os = "macos"))]
 use std::process::Command;
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct PixelColor {
-    r: u8,
-    g: u8,
-    b: u8,
-    hex: String,
-    rgb: String,
-    cmyk: String,
-}
-
-fn rgb_to_cmyk(r: u8, g: u8, b: u8) -> (f64, f64, f64, f64) {
-    if r == 0 && g == 0 && b == 0 {
-        return (0.0, 0.0, 0.0, 100.0);
-    }
-
-    let r_prime = r as f64 / 255.0;
-    let g_prime = g as f64 / 255.0;
-    let b_prime = b as f64 / 255.0;
-
-    let k = 1.0 - r_prime.max(g_prime).max(b_prime);
-    let c = (1.0 - r_prime - k) / (1.0 - k);
-    let m = (1.0 - g_prime - k) / (1.0 - k);
-    let y = (1.0 - b_prime - k) / (1.0 - k);
-
-    (c * 100.0, m * 100.0, y * 100.0, k * 100.0)
-}
-
-fn build_pixel_color(r: u8, g: u8, b: u8) -> Result<PixelColor, String> {
-    let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
-    let rgb_str = format!("rgb({}, {}, {})", r, g, b);
-    let (c, m, y_val, k) = rgb_to_cmyk(r, g, b);
-    let cmyk = format!("cmyk({:.0}%, {:.0}%, {:.0}%, {:.0}%)", c, m, y_val, k);
-
-    Ok(PixelColor {
-        r,
-        g,
-        b,
-        hex,
-        rgb: rgb_str,
-        cmyk,
-    })
-}
-
 // ---- Windows: Win32 API com mouse hook interativo (baseado em wcolor) ----
 #[cfg(target_os = "windows")]
 #[allow(non_snake_case, non_camel_case_types, dead_code)]
@@ -182,7 +138,7 @@ fn platform_get_pixel_color(x: i32, y: i32) -> Result<PixelColor, String> {
         let g = ((color >> 8) & 0xFF) as u8;
         let b = ((color >> 16) & 0xFF) as u8;
 
-        build_pixel_color(r, g, b)
+        Ok(PixelColor::from_rgb(r, g, b))
     }
 }
 
@@ -276,7 +232,7 @@ fn pick_color_interactive() -> Result<PixelColor, String> {
         let g = ((color >> 8) & 0xFF) as u8;
         let b = ((color >> 16) & 0xFF) as u8;
 
-        build_pixel_color(r, g, b)
+        Ok(PixelColor::from_rgb(r, g, b))
     }
 }
 
@@ -318,7 +274,7 @@ fn platform_get_pixel_color(x: i32, y: i32) -> Result<PixelColor, String> {
     let g = data[offset + 1];
     let r = data[offset + 2];
 
-    build_pixel_color(r, g, b)
+    Ok(PixelColor::from_rgb(r, g, b))
 }
 
 // ---- Linux: Portal XDG (ashpd) com fallback para hyprpicker ----
@@ -345,7 +301,7 @@ async fn pick_color_via_portal() -> Result<PixelColor, String> {
     let g = (response.green() * 255.0).round() as u8;
     let b = (response.blue() * 255.0).round() as u8;
 
-    build_pixel_color(r, g, b)
+    Ok(PixelColor::from_rgb(r, g, b))
 }
 
 #[cfg(target_os = "linux")]
@@ -365,20 +321,8 @@ fn pick_color_via_hyprpicker() -> Result<PixelColor, String> {
         return Err(format!("hyprpicker falhou: {}", stderr));
     }
 
-    let hex_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let hex_clean = hex_str.trim_start_matches('#');
-    if hex_clean.len() < 6 {
-        return Err(format!("Formato hex inválido do hyprpicker: {}", hex_str));
-    }
-
-    let r = u8::from_str_radix(&hex_clean[0..2], 16)
-        .map_err(|e| format!("Erro ao parsear R: {}", e))?;
-    let g = u8::from_str_radix(&hex_clean[2..4], 16)
-        .map_err(|e| format!("Erro ao parsear G: {}", e))?;
-    let b = u8::from_str_radix(&hex_clean[4..6], 16)
-        .map_err(|e| format!("Erro ao parsear B: {}", e))?;
-
-    build_pixel_color(r, g, b)
+    let hex_str = String::from_utf8_lossy(&output.stdout);
+    PixelColor::from_hex(&hex_str).map_err(|e| format!("hyprpicker: {}", e))
 }
 
 #[cfg(target_os = "linux")]
@@ -403,7 +347,7 @@ fn get_pixel_color_x11(x: i32, y: i32) -> Result<PixelColor, String> {
         return Err("Dados de pixel inválidos".to_string());
     }
 
-    build_pixel_color(data[2], data[1], data[0])
+    Ok(PixelColor::from_rgb(data[2], data[1], data[0]))
 }
 
 #[cfg(target_os = "linux")]
@@ -0,0 +1,56 @@
+use fd4d_core::documentos;
+use fd4d_core::validacao::ValidationReport;
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_pis(com_mascara: bool, semente: Option<u64>) -> String {
+    documentos::gerar_pis(com_mascara, semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_pis(pis_input: &str) -> ValidationReport {
+    documentos::validar_pis(pis_input)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_cnh(semente: Option<u64>) -> String {
+    documentos::gerar_cnh(semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_cnh(cnh_input: &str) -> ValidationReport {
+    documentos::validar_cnh(cnh_input)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_renavam(semente: Option<u64>) -> String {
+    documentos::gerar_renavam(semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_renavam(renavam_input: &str) -> ValidationReport {
+    documentos::validar_renavam(renavam_input)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_titulo_eleitor(
+    com_mascara: bool,
+    uf: Option<String>,
+    semente: Option<u64>,
+) -> Result<String, String> {
+    documentos::gerar_titulo_eleitor(com_mascara, uf.as_deref(), semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_titulo_eleitor(titulo_input: &str) -> ValidationReport {
+    documentos::validar_titulo_eleitor(titulo_input)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_cns(com_mascara: bool, provisorio: Option<bool>, semente: Option<u64>) -> String {
+    documentos::gerar_cns(com_mascara, provisorio, semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_cns(cns_input: &str) -> ValidationReport {
+    documentos::validar_cns(cns_input)
+}
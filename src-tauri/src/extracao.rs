@@ -0,0 +1,15 @@
+use fd4d_core::extracao::{self, ler_texto_ou_arquivo, ResultadoExtracao};
+
+/// Encontra e valida todos os CPFs/CNPJs de um texto ou arquivo.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn extrair_documentos(
+    texto: Option<String>,
+    caminho: Option<String>,
+) -> Result<ResultadoExtracao, String> {
+    tokio::task::spawn_blocking(move || {
+        let texto = ler_texto_ou_arquivo(texto, caminho)?;
+        Ok(extracao::extrair_documentos(&texto))
+    })
+    .await
+    .map_err(|e| format!("Erro na task de extração: {}", e))?
+}
@@ -0,0 +1,576 @@
+use fd4d_core::cpf_cnpj::TipoDocumento;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const ARQUIVO_ENTRADAS: &str = "historico.ndjson";
+const ARQUIVO_CONFIGURACAO: &str = "historico-configuracao.json";
+/// Retenção padrão; `None` em `max_entradas` deixa o histórico sem limite.
+const MAX_ENTRADAS_PADRAO: usize = 1000;
+/// Linhas descartadas toleradas no NDJSON antes de reescrevê-lo só com as entradas retidas.
+const FOLGA_COMPACTACAO: usize = 500;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EntradaHistorico {
+    id: String,
+    tipo: TipoDocumento,
+    valor: String,
+    /// Milissegundos desde a época Unix, como `Date.now()` no frontend.
+    timestamp: u64,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    fixado: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfiguracaoHistorico {
+    max_entradas: Option<usize>,
+}
+
+impl Default for ConfiguracaoHistorico {
+    fn default() -> Self {
+        ConfiguracaoHistorico {
+            max_entradas: Some(MAX_ENTRADAS_PADRAO),
+        }
+    }
+}
+
+/// Histórico em disco: um NDJSON em que cada documento gerado só acrescenta uma linha, e um
+/// JSON com a configuração. Edições (tags, fixar, remover, importar) reescrevem o NDJSON.
+struct Historico {
+    diretorio: PathBuf,
+    configuracao: ConfiguracaoHistorico,
+    /// Da mais antiga para a mais recente, na ordem do arquivo.
+    entradas: Vec<EntradaHistorico>,
+    /// Linhas no NDJSON, incluindo as que a retenção já descartou da memória.
+    linhas: usize,
+    arquivo: File,
+}
+
+/// Só é preenchido por `inicializar`, no setup do app; até lá nada é registrado.
+static HISTORICO: Lazy<Arc<Mutex<Option<Historico>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatoHistorico {
+    Csv,
+    Json,
+}
+
+impl Historico {
+    /// Abre (ou cria) o histórico em `diretorio`. Linhas ilegíveis do NDJSON, como uma
+    /// escrita interrompida, são descartadas; uma configuração corrompida vira `.bak` e
+    /// volta ao padrão, para não impedir a abertura do app.
+    fn abrir(diretorio: &Path) -> Result<Self, String> {
+        fs::create_dir_all(diretorio)
+            .map_err(|e| format!("Falha ao criar {}: {}", diretorio.display(), e))?;
+
+        let caminho = diretorio.join(ARQUIVO_CONFIGURACAO);
+        let configuracao = match fs::read_to_string(&caminho) {
+            Ok(conteudo) => match serde_json::from_str(&conteudo) {
+                Ok(configuracao) => configuracao,
+                Err(_) => {
+                    fs::rename(&caminho, caminho.with_extension("json.bak"))
+                        .map_err(|e| format!("Falha ao renomear {}: {}", caminho.display(), e))?;
+                    ConfiguracaoHistorico::default()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ConfiguracaoHistorico::default(),
+            Err(e) => return Err(format!("Falha ao ler {}: {}", caminho.display(), e)),
+        };
+
+        let caminho = diretorio.join(ARQUIVO_ENTRADAS);
+        let mut entradas: Vec<EntradaHistorico> = Vec::new();
+        let mut linhas = 0;
+        let mut danificado = false;
+        match File::open(&caminho) {
+            Ok(arquivo) => {
+                for linha in BufReader::new(arquivo).lines() {
+                    let linha =
+                        linha.map_err(|e| format!("Falha ao ler {}: {}", caminho.display(), e))?;
+                    if linha.trim().is_empty() {
+                        continue;
+                    }
+                    linhas += 1;
+                    match serde_json::from_str(&linha) {
+                        Ok(entrada) => entradas.push(entrada),
+                        Err(_) => danificado = true,
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("Falha ao ler {}: {}", caminho.display(), e)),
+        }
+        entradas.sort_by_key(|entrada| entrada.timestamp);
+
+        let mut historico = Historico {
+            diretorio: diretorio.to_path_buf(),
+            configuracao,
+            entradas,
+            linhas,
+            arquivo: Self::abrir_para_acrescentar(&caminho)?,
+        };
+
+        // Sem compactar, o próximo acréscimo se juntaria a uma linha truncada.
+        if danificado {
+            historico.compactar()?;
+        }
+        historico.aplicar_retencao()?;
+        Ok(historico)
+    }
+
+    fn abrir_para_acrescentar(caminho: &Path) -> Result<File, String> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(caminho)
+            .map_err(|e| format!("Falha ao abrir {}: {}", caminho.display(), e))
+    }
+
+    fn caminho_entradas(&self) -> PathBuf {
+        self.diretorio.join(ARQUIVO_ENTRADAS)
+    }
+
+    fn salvar_configuracao(&self) -> Result<(), String> {
+        let caminho = self.diretorio.join(ARQUIVO_CONFIGURACAO);
+        let temporario = caminho.with_extension("json.tmp");
+        let conteudo = serde_json::to_string_pretty(&self.configuracao)
+            .map_err(|e| format!("Falha ao serializar configuração do histórico: {}", e))?;
+        fs::write(&temporario, conteudo)
+            .and_then(|_| fs::rename(&temporario, &caminho))
+            .map_err(|e| format!("Falha ao salvar {}: {}", caminho.display(), e))
+    }
+
+    /// Reescreve o NDJSON só com as entradas retidas.
+    fn compactar(&mut self) -> Result<(), String> {
+        let caminho = self.caminho_entradas();
+        let temporario = caminho.with_extension("ndjson.tmp");
+        let io_err =
+            |e: std::io::Error| format!("Falha ao escrever {}: {}", temporario.display(), e);
+
+        let mut writer = BufWriter::new(File::create(&temporario).map_err(io_err)?);
+        for entrada in &self.entradas {
+            serde_json::to_writer(&mut writer, entrada)
+                .map_err(|e| format!("Falha ao serializar histórico: {}", e))?;
+            writeln!(writer).map_err(io_err)?;
+        }
+        writer.flush().map_err(io_err)?;
+        drop(writer);
+
+        fs::rename(&temporario, &caminho)
+            .map_err(|e| format!("Falha ao salvar {}: {}", caminho.display(), e))?;
+        self.arquivo = Self::abrir_para_acrescentar(&caminho)?;
+        self.linhas = self.entradas.len();
+        Ok(())
+    }
+
+    /// Descarta as entradas mais antigas não fixadas até caber na retenção configurada.
+    fn aplicar_retencao(&mut self) -> Result<(), String> {
+        if let Some(max) = self.configuracao.max_entradas {
+            let mut excedente = self.entradas.len().saturating_sub(max);
+            self.entradas.retain(|entrada| {
+                let descartar = excedente > 0 && !entrada.fixado;
+                if descartar {
+                    excedente -= 1;
+                }
+                !descartar
+            });
+        }
+
+        if self.linhas > self.entradas.len() + FOLGA_COMPACTACAO {
+            self.compactar()?;
+        }
+        Ok(())
+    }
+
+    fn registrar(&mut self, tipo: TipoDocumento, valor: &str) -> Result<(), String> {
+        let entrada = EntradaHistorico {
+            id: uuid::Uuid::new_v4().to_string(),
+            tipo,
+            valor: valor.to_string(),
+            timestamp: agora_ms(),
+            tags: Vec::new(),
+            fixado: false,
+        };
+
+        let mut linha = serde_json::to_string(&entrada)
+            .map_err(|e| format!("Falha ao serializar histórico: {}", e))?;
+        linha.push('\n');
+        self.arquivo.write_all(linha.as_bytes()).map_err(|e| {
+            format!(
+                "Falha ao escrever {}: {}",
+                self.caminho_entradas().display(),
+                e
+            )
+        })?;
+        self.linhas += 1;
+
+        self.entradas.push(entrada);
+        self.aplicar_retencao()
+    }
+
+    fn importar(&mut self, itens: Vec<ItemImportado>) -> Result<usize, String> {
+        let mut novas: HashSet<String> = HashSet::new();
+        for item in itens {
+            let existe = self.entradas.iter().any(|entrada| {
+                entrada.tipo == item.tipo
+                    && entrada.valor == item.valor
+                    && entrada.timestamp == item.timestamp
+            });
+            if !existe {
+                let id = uuid::Uuid::new_v4().to_string();
+                novas.insert(id.clone());
+                self.entradas.push(EntradaHistorico {
+                    id,
+                    tipo: item.tipo,
+                    valor: item.valor,
+                    timestamp: item.timestamp,
+                    tags: Vec::new(),
+                    fixado: false,
+                });
+            }
+        }
+
+        self.entradas.sort_by_key(|entrada| entrada.timestamp);
+        self.aplicar_retencao()?;
+        self.compactar()?;
+        Ok(self
+            .entradas
+            .iter()
+            .filter(|entrada| novas.contains(&entrada.id))
+            .count())
+    }
+
+    fn entrada_mut(&mut self, id: &str) -> Result<&mut EntradaHistorico, String> {
+        self.entradas
+            .iter_mut()
+            .find(|entrada| entrada.id == id)
+            .ok_or_else(|| format!("Entrada não encontrada: {}", id))
+    }
+}
+
+fn agora_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// Carrega o histórico de `diretorio`.
+pub(crate) fn inicializar(diretorio: PathBuf) -> Result<(), String> {
+    let historico = Historico::abrir(&diretorio)?;
+    *HISTORICO.lock().unwrap() = Some(historico);
+    Ok(())
+}
+
+fn com_historico<T>(f: impl FnOnce(&mut Historico) -> Result<T, String>) -> Result<T, String> {
+    let mut guard = HISTORICO.lock().unwrap();
+    let historico = guard
+        .as_mut()
+        .ok_or_else(|| "Histórico não inicializado".to_string())?;
+    f(historico)
+}
+
+/// Registra um documento gerado. Antes de `inicializar` não faz nada. O histórico é
+/// secundário: uma falha de escrita só é logada e o documento chega ao usuário mesmo assim.
+pub(crate) fn registrar(tipo: TipoDocumento, valor: &str) {
+    let resultado = match HISTORICO.lock().unwrap().as_mut() {
+        Some(historico) => historico.registrar(tipo, valor),
+        None => Ok(()),
+    };
+    if let Err(e) = resultado {
+        eprintln!("Falha ao registrar no histórico: {}", e);
+    }
+}
+
+/// Lista o histórico, fixados primeiro e depois do mais recente para o mais antigo.
+/// `busca` procura no valor (com ou sem máscara) e nas tags.
+#[tauri::command(rename_all = "camelCase")]
+pub fn listar_historico(
+    tipo: Option<TipoDocumento>,
+    busca: Option<String>,
+    tag: Option<String>,
+) -> Result<Vec<EntradaHistorico>, String> {
+    let busca = busca.map(|b| b.trim().to_lowercase()).unwrap_or_default();
+    let busca_normalizada: String = busca.chars().filter(|c| c.is_alphanumeric()).collect();
+
+    com_historico(|historico| {
+        let mut entradas: Vec<EntradaHistorico> = historico
+            .entradas
+            .iter()
+            .rev()
+            .filter(|entrada| tipo.is_none_or(|tipo| entrada.tipo == tipo))
+            .filter(|entrada| tag.as_ref().is_none_or(|tag| entrada.tags.contains(tag)))
+            .filter(|entrada| {
+                if busca.is_empty() {
+                    return true;
+                }
+                let valor = entrada.valor.to_lowercase();
+                let valor_normalizado: String =
+                    valor.chars().filter(|c| c.is_alphanumeric()).collect();
+                valor.contains(&busca)
+                    || (!busca_normalizada.is_empty()
+                        && valor_normalizado.contains(&busca_normalizada))
+                    || entrada
+                        .tags
+                        .iter()
+                        .any(|t| t.to_lowercase().contains(&busca))
+            })
+            .cloned()
+            .collect();
+        // Ordenação estável: preserva a ordem cronológica dentro de cada grupo.
+        entradas.sort_by_key(|entrada| !entrada.fixado);
+        Ok(entradas)
+    })
+}
+
+/// Substitui as tags de uma entrada. Tags vazias ou repetidas são descartadas.
+#[tauri::command(rename_all = "camelCase")]
+pub fn marcar_historico(id: String, tags: Vec<String>) -> Result<EntradaHistorico, String> {
+    com_historico(|historico| {
+        let entrada = historico.entrada_mut(&id)?;
+        entrada.tags.clear();
+        for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !entrada.tags.iter().any(|t| t == tag) {
+                entrada.tags.push(tag.to_string());
+            }
+        }
+        let entrada = entrada.clone();
+        historico.compactar()?;
+        Ok(entrada)
+    })
+}
+
+/// Entradas fixadas não são removidas pela retenção nem por `limpar_historico`.
+#[tauri::command(rename_all = "camelCase")]
+pub fn fixar_historico(id: String, fixado: bool) -> Result<EntradaHistorico, String> {
+    com_historico(|historico| {
+        let entrada = historico.entrada_mut(&id)?;
+        entrada.fixado = fixado;
+        let entrada = entrada.clone();
+        if !fixado {
+            historico.aplicar_retencao()?;
+        }
+        historico.compactar()?;
+        Ok(entrada)
+    })
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn remover_historico(id: String) -> Result<(), String> {
+    com_historico(|historico| {
+        let antes = historico.entradas.len();
+        historico.entradas.retain(|entrada| entrada.id != id);
+        if historico.entradas.len() == antes {
+            return Err(format!("Entrada não encontrada: {}", id));
+        }
+        historico.compactar()
+    })
+}
+
+/// Remove as entradas não fixadas do tipo informado (ou de todos os tipos).
+#[tauri::command(rename_all = "camelCase")]
+pub fn limpar_historico(tipo: Option<TipoDocumento>) -> Result<(), String> {
+    com_historico(|historico| {
+        historico
+            .entradas
+            .retain(|entrada| entrada.fixado || tipo.is_some_and(|tipo| entrada.tipo != tipo));
+        historico.compactar()
+    })
+}
+
+#[tauri::command]
+pub fn obter_configuracao_historico() -> Result<ConfiguracaoHistorico, String> {
+    com_historico(|historico| Ok(historico.configuracao.clone()))
+}
+
+/// Define a retenção do histórico; `maxEntradas` nulo mantém tudo.
+#[tauri::command(rename_all = "camelCase")]
+pub fn configurar_historico(max_entradas: Option<usize>) -> Result<ConfiguracaoHistorico, String> {
+    if max_entradas == Some(0) {
+        return Err("A retenção deve ser de pelo menos 1 entrada".into());
+    }
+
+    com_historico(|historico| {
+        historico.configuracao.max_entradas = max_entradas;
+        historico.aplicar_retencao()?;
+        historico.salvar_configuracao()?;
+        Ok(historico.configuracao.clone())
+    })
+}
+
+/// Exporta as entradas filtradas por `tipo` para `caminho`. Retorna quantas foram escritas.
+#[tauri::command(rename_all = "camelCase")]
+pub fn exportar_historico(
+    caminho: String,
+    formato: FormatoHistorico,
+    tipo: Option<TipoDocumento>,
+) -> Result<usize, String> {
+    let entradas = listar_historico(tipo, None, None)?;
+
+    let file = File::create(&caminho).map_err(|e| format!("Falha ao criar {}: {}", caminho, e))?;
+    let mut writer = BufWriter::new(file);
+    let io_err = |e: std::io::Error| format!("Falha ao escrever {}: {}", caminho, e);
+
+    match formato {
+        FormatoHistorico::Json => serde_json::to_writer_pretty(&mut writer, &entradas)
+            .map_err(|e| format!("Falha ao escrever {}: {}", caminho, e))?,
+        FormatoHistorico::Csv => {
+            writeln!(writer, "tipo,valor,timestamp,tags,fixado").map_err(io_err)?;
+            for entrada in &entradas {
+                let tipo = match entrada.tipo {
+                    TipoDocumento::Cpf => "cpf",
+                    TipoDocumento::Cnpj => "cnpj",
+                };
+                writeln!(
+                    writer,
+                    "{},{},{},\"{}\",{}",
+                    tipo,
+                    entrada.valor,
+                    entrada.timestamp,
+                    entrada.tags.join(";").replace('"', "\"\""),
+                    entrada.fixado
+                )
+                .map_err(io_err)?;
+            }
+        }
+    }
+
+    writer.flush().map_err(io_err)?;
+    Ok(entradas.len())
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemImportado {
+    tipo: TipoDocumento,
+    valor: String,
+    timestamp: u64,
+}
+
+/// Importa entradas antigas (ex.: o histórico que ficava no `localStorage`), ignorando as
+/// que já existem. Retorna quantas foram adicionadas e sobreviveram à retenção.
+#[tauri::command(rename_all = "camelCase")]
+pub fn importar_historico(itens: Vec<ItemImportado>) -> Result<usize, String> {
+    com_historico(|historico| historico.importar(itens))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diretorio_temporario() -> PathBuf {
+        std::env::temp_dir().join(format!("fd4d-historico-{}", uuid::Uuid::new_v4()))
+    }
+
+    fn item(valor: &str, timestamp: u64) -> ItemImportado {
+        ItemImportado {
+            tipo: TipoDocumento::Cpf,
+            valor: valor.to_string(),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn registrar_acrescenta_uma_linha_e_persiste() {
+        let diretorio = diretorio_temporario();
+        let mut historico = Historico::abrir(&diretorio).unwrap();
+        historico
+            .registrar(TipoDocumento::Cpf, "529.982.247-25")
+            .unwrap();
+        historico
+            .registrar(TipoDocumento::Cnpj, "11222333000181")
+            .unwrap();
+
+        let conteudo = fs::read_to_string(diretorio.join(ARQUIVO_ENTRADAS)).unwrap();
+        assert_eq!(conteudo.lines().count(), 2);
+
+        let historico = Historico::abrir(&diretorio).unwrap();
+        let valores: Vec<&str> = historico
+            .entradas
+            .iter()
+            .map(|e| e.valor.as_str())
+            .collect();
+        assert_eq!(valores, ["529.982.247-25", "11222333000181"]);
+        fs::remove_dir_all(&diretorio).unwrap();
+    }
+
+    #[test]
+    fn retencao_descarta_as_mais_antigas_nao_fixadas() {
+        let diretorio = diretorio_temporario();
+        let mut historico = Historico::abrir(&diretorio).unwrap();
+        historico.configuracao.max_entradas = Some(2);
+        for valor in ["1", "2", "3"] {
+            historico.registrar(TipoDocumento::Cpf, valor).unwrap();
+        }
+        let valores: Vec<&str> = historico
+            .entradas
+            .iter()
+            .map(|e| e.valor.as_str())
+            .collect();
+        assert_eq!(valores, ["2", "3"]);
+
+        historico.entradas[0].fixado = true;
+        historico.registrar(TipoDocumento::Cpf, "4").unwrap();
+        let valores: Vec<&str> = historico
+            .entradas
+            .iter()
+            .map(|e| e.valor.as_str())
+            .collect();
+        assert_eq!(valores, ["2", "4"]);
+        fs::remove_dir_all(&diretorio).unwrap();
+    }
+
+    #[test]
+    fn importar_conta_so_as_que_sobreviveram_a_retencao() {
+        let diretorio = diretorio_temporario();
+        let mut historico = Historico::abrir(&diretorio).unwrap();
+        historico.configuracao.max_entradas = Some(2);
+        historico.registrar(TipoDocumento::Cpf, "recente").unwrap();
+
+        let itens = vec![
+            item("antigo-1", 1),
+            item("antigo-2", 2),
+            item("antigo-3", 3),
+        ];
+        assert_eq!(historico.importar(itens.clone()).unwrap(), 1);
+        assert_eq!(historico.importar(itens).unwrap(), 0);
+        let valores: Vec<&str> = historico
+            .entradas
+            .iter()
+            .map(|e| e.valor.as_str())
+            .collect();
+        assert_eq!(valores, ["antigo-3", "recente"]);
+        fs::remove_dir_all(&diretorio).unwrap();
+    }
+
+    #[test]
+    fn reabre_descartando_linha_truncada() {
+        let diretorio = diretorio_temporario();
+        let mut historico = Historico::abrir(&diretorio).unwrap();
+        historico
+            .registrar(TipoDocumento::Cpf, "529.982.247-25")
+            .unwrap();
+        drop(historico);
+
+        let caminho = diretorio.join(ARQUIVO_ENTRADAS);
+        let mut arquivo = OpenOptions::new().append(true).open(&caminho).unwrap();
+        write!(arquivo, "{{\"id\":\"trunc").unwrap();
+        drop(arquivo);
+
+        let mut historico = Historico::abrir(&diretorio).unwrap();
+        assert_eq!(historico.entradas.len(), 1);
+        historico
+            .registrar(TipoDocumento::Cpf, "111.444.777-35")
+            .unwrap();
+        assert_eq!(Historico::abrir(&diretorio).unwrap().entradas.len(), 2);
+        fs::remove_dir_all(&diretorio).unwrap();
+    }
+}
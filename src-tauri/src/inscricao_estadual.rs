@@ -0,0 +1,17 @@
+use fd4d_core::inscricao_estadual;
+use fd4d_core::validacao::ValidationReport;
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_inscricao_estadual(
+    uf: &str,
+    com_mascara: bool,
+    produtor_rural: Option<bool>,
+    semente: Option<u64>,
+) -> Result<String, String> {
+    inscricao_estadual::gerar_inscricao_estadual(uf, com_mascara, produtor_rural, semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_inscricao_estadual(uf: &str, ie_input: &str) -> Result<ValidationReport, String> {
+    inscricao_estadual::validar_inscricao_estadual(uf, ie_input)
+}
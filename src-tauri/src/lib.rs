@@ -1,14 +1,50 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
+use tauri::Manager;
+
+mod anonimizacao;
+mod boleto;
+mod chave_acesso;
 mod color_picker;
 mod cpf_cnpj;
+mod documentos;
+mod extracao;
+mod historico;
+mod inscricao_estadual;
+mod lote;
+mod perfis;
+mod pix;
+mod placa;
 mod webhook;
 
+pub use anonimizacao::{anonimizar_documentos, nova_sessao_anonimizacao};
+pub use boleto::{converter_boleto, gerar_boleto, validar_boleto};
+pub use chave_acesso::{decodificar_chave_acesso, gerar_chave_acesso};
 pub use color_picker::{get_pixel_color, pick_color_portal};
-pub use cpf_cnpj::{gerar_cnpj, gerar_cpf, validar_cnpj, validar_cpf};
+pub use cpf_cnpj::{
+    formatar_documento, gerar_cnpj, gerar_cpf, gerar_documentos_invalidos, gerar_familia_cnpj,
+    mesma_raiz_cnpj, normalizar_documento, validar_cnpj, validar_cpf,
+};
+pub use documentos::{
+    gerar_cnh, gerar_cns, gerar_pis, gerar_renavam, gerar_titulo_eleitor, validar_cnh, validar_cns,
+    validar_pis, validar_renavam, validar_titulo_eleitor,
+};
+pub use extracao::extrair_documentos;
+pub use historico::{
+    configurar_historico, exportar_historico, fixar_historico, importar_historico,
+    limpar_historico, listar_historico, marcar_historico, obter_configuracao_historico,
+    remover_historico,
+};
+pub use inscricao_estadual::{gerar_inscricao_estadual, validar_inscricao_estadual};
+pub use lote::gerar_documentos_em_lote;
+pub use perfis::{exportar_perfis, gerar_empresa, gerar_pessoa};
+pub use pix::{gerar_br_code, ler_br_code, validar_chave_pix};
+pub use placa::{converter_placa, gerar_placa, validar_placa};
 pub use webhook::{
-    clear_webhook_requests, get_webhook_requests, send_webhook_request, start_webhook_server,
-    stop_webhook_server,
+    clear_webhook_requests, delete_webhook_rule, get_webhook_faults, get_webhook_retention,
+    list_webhook_requests, list_webhook_rules, list_webhook_sessions, save_webhook_rule,
+    send_webhook_request, set_webhook_faults, set_webhook_retention, set_webhook_rules,
+    start_webhook_server, stop_webhook_server,
 };
 
 #[tauri::command]
@@ -22,16 +58,74 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            historico::inicializar(app.path().app_data_dir()?)?;
+            webhook::inicializar(app.handle().clone(), app.path().app_data_dir()?)?;
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             gerar_cpf,
             gerar_cnpj,
             validar_cpf,
             validar_cnpj,
+            gerar_familia_cnpj,
+            mesma_raiz_cnpj,
+            formatar_documento,
+            normalizar_documento,
+            gerar_documentos_invalidos,
+            gerar_placa,
+            validar_placa,
+            converter_placa,
+            gerar_boleto,
+            validar_boleto,
+            converter_boleto,
+            validar_chave_pix,
+            gerar_br_code,
+            ler_br_code,
+            gerar_chave_acesso,
+            decodificar_chave_acesso,
+            gerar_documentos_em_lote,
+            gerar_pessoa,
+            gerar_empresa,
+            exportar_perfis,
+            gerar_pis,
+            validar_pis,
+            gerar_cnh,
+            validar_cnh,
+            gerar_renavam,
+            validar_renavam,
+            gerar_titulo_eleitor,
+            validar_titulo_eleitor,
+            gerar_cns,
+            validar_cns,
+            gerar_inscricao_estadual,
+            validar_inscricao_estadual,
+            extrair_documentos,
+            anonimizar_documentos,
+            nova_sessao_anonimizacao,
+            listar_historico,
+            marcar_historico,
+            fixar_historico,
+            remover_historico,
+            limpar_historico,
+            importar_historico,
+            obter_configuracao_historico,
+            configurar_historico,
+            exportar_historico,
             start_webhook_server,
             stop_webhook_server,
-            get_webhook_requests,
+            list_webhook_requests,
+            list_webhook_sessions,
             clear_webhook_requests,
+            get_webhook_retention,
+            set_webhook_retention,
+            list_webhook_rules,
+            save_webhook_rule,
+            delete_webhook_rule,
+            set_webhook_rules,
+            get_webhook_faults,
+            set_webhook_faults,
             send_webhook_request,
             get_pixel_color,
             pick_color_portal
@@ -0,0 +1,8 @@
+use fd4d_core::lote::{self, OpcoesLote, ResultadoLote};
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn gerar_documentos_em_lote(opcoes: OpcoesLote) -> Result<ResultadoLote, String> {
+    tokio::task::spawn_blocking(move || lote::gerar_documentos_em_lote(&opcoes))
+        .await
+        .map_err(|e| format!("Erro na task de geração em lote: {}", e))?
+}
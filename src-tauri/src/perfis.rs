@@ -0,0 +1,33 @@
+use fd4d_core::lote::ResultadoLote;
+use fd4d_core::perfis::{self, Empresa, OpcoesPerfis, Pessoa};
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_pessoa(
+    uf: Option<String>,
+    com_mascara: bool,
+    semente: Option<u64>,
+) -> Result<Pessoa, String> {
+    perfis::gerar_pessoa(uf.as_deref(), com_mascara, semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_empresa(
+    uf: Option<String>,
+    com_mascara: bool,
+    alfanumerico: Option<bool>,
+    semente: Option<u64>,
+) -> Result<Empresa, String> {
+    perfis::gerar_empresa(
+        uf.as_deref(),
+        com_mascara,
+        alfanumerico.unwrap_or(false),
+        semente,
+    )
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub async fn exportar_perfis(opcoes: OpcoesPerfis) -> Result<ResultadoLote, String> {
+    tokio::task::spawn_blocking(move || perfis::exportar_perfis(&opcoes))
+        .await
+        .map_err(|e| format!("Erro na task de exportação de perfis: {}", e))?
+}
@@ -0,0 +1,16 @@
+use fd4d_core::pix::{self, DadosBrCode, LeituraBrCode, TipoChavePix, ValidacaoChavePix};
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_chave_pix(chave: &str, tipo: Option<TipoChavePix>) -> ValidacaoChavePix {
+    pix::validar_chave_pix(chave, tipo)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_br_code(dados: DadosBrCode) -> Result<String, String> {
+    pix::gerar_br_code(&dados)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn ler_br_code(payload: &str) -> LeituraBrCode {
+    pix::ler_br_code(payload)
+}
@@ -0,0 +1,21 @@
+use fd4d_core::placa::{self, FormatoPlaca, PlacaGerada, ValidacaoPlaca};
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn gerar_placa(
+    formato: Option<FormatoPlaca>,
+    com_mascara: bool,
+    com_renavam: Option<bool>,
+    semente: Option<u64>,
+) -> PlacaGerada {
+    placa::gerar_placa(formato, com_mascara, com_renavam.unwrap_or(false), semente)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn validar_placa(placa_input: &str) -> ValidacaoPlaca {
+    placa::validar_placa(placa_input)
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn converter_placa(placa_input: &str) -> Result<String, String> {
+    placa::converter_placa(placa_input)
+}
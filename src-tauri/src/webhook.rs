@@ -1,138 +1,87 @@
-use axum::{
-    extract::{Json, State},
-    http::{HeaderMap, StatusCode},
-    routing::{get, post},
-    Router,
+use fd4d_core::webhook::{self, SendWebhookResponse, WebhookCapture, WebhookEvent, WebhookServer};
+use fd4d_core::webhook_faults::FaultConfig;
+use fd4d_core::webhook_rules::ResponseRule;
+use fd4d_core::webhook_store::{
+    WebhookPage, WebhookQuery, WebhookRetention, WebhookSession, WebhookStore,
 };
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use tower_http::cors::CorsLayer;
-
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct WebhookRequest {
-    id: String,
-    timestamp: String,
-    method: String,
-    path: String,
-    headers: HashMap<String, String>,
-    body: String,
-    status_code: u16,
-    error_reason: Option<String>,
-}
-
-#[derive(Clone)]
-struct WebhookState {
-    requests: Arc<Mutex<Vec<WebhookRequest>>>,
-    auth_method: Arc<Mutex<String>>,
-    auth_value: Arc<Mutex<String>>,
-}
-
-static WEBHOOK_STATE: Lazy<WebhookState> = Lazy::new(|| WebhookState {
-    requests: Arc::new(Mutex::new(Vec::new())),
-    auth_method: Arc::new(Mutex::new(String::new())),
-    auth_value: Arc::new(Mutex::new(String::new())),
-});
-
-type ServerHandle = tokio::task::JoinHandle<()>;
-static SERVER_HANDLE: Lazy<Arc<Mutex<Option<ServerHandle>>>> =
-    Lazy::new(|| Arc::new(Mutex::new(None)));
-
-async fn handle_webhook(
-    State(state): State<WebhookState>,
-    headers: HeaderMap,
-    body: String,
-) -> (StatusCode, Json<serde_json::Value>) {
-    let auth_method = state.auth_method.lock().unwrap().clone();
-    let auth_value = state.auth_value.lock().unwrap().clone();
-
-    let mut status_code = 200;
-    let mut error_reason: Option<String> = None;
-
-    if !auth_method.is_empty() && !auth_value.is_empty() {
-        let auth_valid = match auth_method.as_str() {
-            "bearer" => {
-                if let Some(auth_header) = headers.get("authorization") {
-                    if let Ok(value) = auth_header.to_str() {
-                        value == format!("Bearer {}", auth_value) || value == auth_value
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            }
-            "api-key" => {
-                if let Some(api_key) = headers.get("x-api-key") {
-                    if let Ok(value) = api_key.to_str() {
-                        value == auth_value
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+static CAPTURE: Lazy<WebhookCapture> = Lazy::new(WebhookCapture::new);
+
+static SERVER: Lazy<Arc<Mutex<Option<WebhookServer>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Só é preenchido por `inicializar`, no setup do app; até lá nada é gravado.
+static STORE: Lazy<Arc<Mutex<Option<WebhookStore>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
+
+/// Abre o store em `diretorio/webhook`, aplica as regras e falhas salvas, grava nele cada
+/// captura e sessão e repassa as capturas para `webhook://request` e o estado do servidor
+/// para `webhook://lifecycle`.
+pub(crate) fn inicializar(app: AppHandle, diretorio: PathBuf) -> Result<(), String> {
+    let store = WebhookStore::open(&diretorio.join("webhook"))?;
+    CAPTURE.set_rules(store.rules());
+    CAPTURE.set_faults(store.faults());
+    *STORE.lock().unwrap() = Some(store);
+
+    // O listener roda dentro do handler HTTP: a escrita em disco fica numa thread própria,
+    // que recebe os eventos na ordem em que aconteceram.
+    let (gravacao, eventos) = mpsc::channel::<WebhookEvent>();
+    std::thread::Builder::new()
+        .name("webhook-store".to_string())
+        .spawn(move || {
+            for event in eventos {
+                persistir(&event);
             }
-            "basic" => {
-                if let Some(auth_header) = headers.get("authorization") {
-                    if let Ok(value) = auth_header.to_str() {
-                        value == format!("Basic {}", auth_value) || value == auth_value
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
+        })
+        .map_err(|e| format!("Failed to start webhook store writer: {}", e))?;
+
+    CAPTURE.set_listener(move |event| {
+        if gravacao.send(event.clone()).is_err() {
+            eprintln!("Webhook store writer stopped; capture not saved");
+        }
+        let result = match event {
+            WebhookEvent::Request(request) => app.emit("webhook://request", request),
+            WebhookEvent::Crashed { .. } => {
+                // O servidor morreu sozinho; libera o slot para poder reiniciar.
+                SERVER.lock().unwrap().take();
+                app.emit("webhook://lifecycle", event)
             }
-            _ => true,
+            _ => app.emit("webhook://lifecycle", event),
         };
-
-        if !auth_valid {
-            status_code = 401;
-            error_reason = Some("Authentication failed: Invalid credentials".to_string());
+        if let Err(e) = result {
+            eprintln!("Failed to emit webhook event: {}", e);
         }
-    }
-
-    let headers_map: HashMap<String, String> = headers
-        .iter()
-        .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
-        .collect();
+    });
+    Ok(())
+}
 
-    let request = WebhookRequest {
-        id: uuid::Uuid::new_v4().to_string(),
-        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-        method: "POST".to_string(),
-        path: "/webhook".to_string(),
-        headers: headers_map,
-        body: body.clone(),
-        status_code,
-        error_reason: error_reason.clone(),
+/// Falhas só são logadas: o disco nunca impede o servidor de responder.
+fn persistir(event: &WebhookEvent) {
+    let mut guard = STORE.lock().unwrap();
+    let Some(store) = guard.as_mut() else {
+        return;
     };
 
-    state.requests.lock().unwrap().push(request);
-
-    let response_body = if status_code == 200 {
-        serde_json::json!({
-            "success": true,
-            "message": "Webhook received successfully"
-        })
-    } else {
-        serde_json::json!({
-            "success": false,
-            "error": error_reason.unwrap_or_else(|| "Unknown error".to_string())
-        })
+    let result = match event {
+        WebhookEvent::Request(request) => store.append((**request).clone()),
+        WebhookEvent::Started { url } => store.start_session(url).map(|_| ()),
+        WebhookEvent::Stopped | WebhookEvent::Crashed { .. } => store.end_session(),
+        WebhookEvent::BindError { .. } => Ok(()),
     };
-
-    (
-        StatusCode::from_u16(status_code).unwrap(),
-        Json(response_body),
-    )
+    if let Err(e) = result {
+        eprintln!("{}", e);
+    }
 }
 
-async fn health_check() -> &'static str {
-    "Webhook server is running"
+fn com_store<T>(f: impl FnOnce(&mut WebhookStore) -> Result<T, String>) -> Result<T, String> {
+    let mut guard = STORE.lock().unwrap();
+    let store = guard
+        .as_mut()
+        .ok_or_else(|| "Webhook store not initialized".to_string())?;
+    f(store)
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -141,68 +90,109 @@ pub async fn start_webhook_server(
     auth_method: String,
     auth_value: String,
 ) -> Result<String, String> {
-    {
-        let handle_guard = SERVER_HANDLE.lock().unwrap();
-        if handle_guard.is_some() {
-            return Err("Server is already running".to_string());
-        }
+    if SERVER.lock().unwrap().is_some() {
+        return Err("Server is already running".to_string());
     }
 
-    WEBHOOK_STATE.requests.lock().unwrap().clear();
-    *WEBHOOK_STATE.auth_method.lock().unwrap() = auth_method;
-    *WEBHOOK_STATE.auth_value.lock().unwrap() = auth_value;
+    CAPTURE.reset_fault_counters();
+    CAPTURE.set_auth(auth_method, auth_value);
 
-    let app = Router::new()
-        .route("/webhook", post(handle_webhook))
-        .route("/health", get(health_check))
-        .layer(CorsLayer::permissive())
-        .with_state(WEBHOOK_STATE.clone());
+    let server = WebhookServer::start(port, &CAPTURE).await?;
+    let url = server.url();
+    *SERVER.lock().unwrap() = Some(server);
 
-    let addr = format!("0.0.0.0:{}", port);
-    let listener = match tokio::net::TcpListener::bind(&addr).await {
-        Ok(l) => l,
-        Err(e) => return Err(format!("Failed to bind to {}: {}", addr, e)),
-    };
+    Ok(url)
+}
 
-    let handle = tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app).await {
-            eprintln!("Server error: {}", e);
+#[tauri::command]
+pub async fn stop_webhook_server() -> Result<(), String> {
+    match SERVER.lock().unwrap().take() {
+        Some(server) => {
+            server.stop();
+            Ok(())
         }
-    });
+        None => Err("No server is running".to_string()),
+    }
+}
 
-    *SERVER_HANDLE.lock().unwrap() = Some(handle);
+/// Capturas gravadas, da mais recente para a mais antiga, filtradas e paginadas.
+#[tauri::command(rename_all = "camelCase")]
+pub fn list_webhook_requests(query: WebhookQuery) -> Result<WebhookPage, String> {
+    com_store(|store| Ok(store.query(&query)))
+}
 
-    Ok(format!("http://localhost:{}/webhook", port))
+#[tauri::command]
+pub fn list_webhook_sessions() -> Result<Vec<WebhookSession>, String> {
+    com_store(|store| Ok(store.sessions()))
+}
+
+/// Apaga as capturas de `sessionId` ou, sem ele, todas as gravadas.
+#[tauri::command(rename_all = "camelCase")]
+pub fn clear_webhook_requests(session_id: Option<String>) -> Result<(), String> {
+    com_store(|store| store.clear(session_id.as_deref()))
 }
 
 #[tauri::command]
-pub async fn stop_webhook_server() -> Result<(), String> {
-    let mut handle_guard = SERVER_HANDLE.lock().unwrap();
-    if let Some(handle) = handle_guard.take() {
-        handle.abort();
-        Ok(())
-    } else {
-        Err("No server is running".to_string())
-    }
+pub fn get_webhook_retention() -> Result<WebhookRetention, String> {
+    com_store(|store| Ok(store.retention()))
+}
+
+/// Define a retenção por quantidade e/ou idade; campos nulos não limitam.
+#[tauri::command(rename_all = "camelCase")]
+pub fn set_webhook_retention(retention: WebhookRetention) -> Result<WebhookRetention, String> {
+    com_store(|store| {
+        store.set_retention(retention)?;
+        Ok(store.retention())
+    })
+}
+
+/// Altera as regras salvas e aplica o resultado ao servidor, rodando ou não.
+fn com_regras<T>(f: impl FnOnce(&mut WebhookStore) -> Result<T, String>) -> Result<T, String> {
+    com_store(|store| {
+        let resultado = f(store)?;
+        CAPTURE.set_rules(store.rules());
+        Ok(resultado)
+    })
 }
 
 #[tauri::command]
-pub fn get_webhook_requests() -> Vec<WebhookRequest> {
-    WEBHOOK_STATE.requests.lock().unwrap().clone()
+pub fn list_webhook_rules() -> Result<Vec<ResponseRule>, String> {
+    com_store(|store| Ok(store.rules()))
+}
+
+/// Cria (sem `id`) ou atualiza uma regra de resposta simulada.
+#[tauri::command(rename_all = "camelCase")]
+pub fn save_webhook_rule(rule: ResponseRule) -> Result<ResponseRule, String> {
+    com_regras(|store| store.save_rule(rule))
+}
+
+#[tauri::command(rename_all = "camelCase")]
+pub fn delete_webhook_rule(id: String) -> Result<(), String> {
+    com_regras(|store| store.delete_rule(&id))
+}
+
+/// Substitui todas as regras; a ordem da lista é a ordem de avaliação.
+#[tauri::command(rename_all = "camelCase")]
+pub fn set_webhook_rules(rules: Vec<ResponseRule>) -> Result<Vec<ResponseRule>, String> {
+    com_regras(|store| {
+        store.set_rules(rules)?;
+        Ok(store.rules())
+    })
 }
 
 #[tauri::command]
-pub fn clear_webhook_requests() {
-    WEBHOOK_STATE.requests.lock().unwrap().clear();
+pub fn get_webhook_faults() -> Result<FaultConfig, String> {
+    com_store(|store| Ok(store.faults()))
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct SendWebhookResponse {
-    status_code: u16,
-    headers: HashMap<String, String>,
-    body: String,
-    error_reason: Option<String>,
+/// Define as falhas globais e recomeça a contagem do `failFirst`.
+#[tauri::command(rename_all = "camelCase")]
+pub fn set_webhook_faults(faults: FaultConfig) -> Result<FaultConfig, String> {
+    com_store(|store| {
+        store.set_faults(faults)?;
+        CAPTURE.set_faults(store.faults());
+        Ok(store.faults())
+    })
 }
 
 #[tauri::command(rename_all = "camelCase")]
@@ -212,54 +202,5 @@ pub async fn send_webhook_request(
     headers: HashMap<String, String>,
     body: String,
 ) -> Result<SendWebhookResponse, String> {
-    let client = reqwest::Client::new();
-
-    let mut request_builder = match method.to_uppercase().as_str() {
-        "GET" => client.get(&url),
-        "POST" => client.post(&url),
-        "PUT" => client.put(&url),
-        "DELETE" => client.delete(&url),
-        "PATCH" => client.patch(&url),
-        _ => return Err(format!("Unsupported HTTP method: {}", method)),
-    };
-
-    for (key, value) in &headers {
-        request_builder = request_builder.header(key.as_str(), value.as_str());
-    }
-
-    if !body.is_empty() && method.to_uppercase() != "GET" {
-        request_builder = request_builder
-            .header("content-type", "application/json")
-            .body(body);
-    }
-
-    match request_builder.send().await {
-        Ok(response) => {
-            let status_code = response.status().as_u16();
-            let resp_headers: HashMap<String, String> = response
-                .headers()
-                .iter()
-                .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
-                .collect();
-            let resp_body = response.text().await.unwrap_or_default();
-
-            let error_reason = if status_code >= 400 {
-                Some(format!(
-                    "HTTP {}: {}",
-                    status_code,
-                    resp_body.chars().take(200).collect::<String>()
-                ))
-            } else {
-                None
-            };
-
-            Ok(SendWebhookResponse {
-                status_code,
-                headers: resp_headers,
-                body: resp_body,
-                error_reason,
-            })
-        }
-        Err(e) => Err(format!("Request failed: {}", e)),
-    }
+    webhook::send_webhook_request(url, method, headers, body).await
 }
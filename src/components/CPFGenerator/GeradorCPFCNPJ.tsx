@@ -3,7 +3,6 @@ import { BorderBeam } from "../ui/border-beam";
 import { ShimmerButton } from "../ui/shimmer-button";
 import { TypingAnimation } from "../ui/typing-animation";
 import { gerarCpf, gerarCnpj, copiarParaClipboard } from "./gerador";
-import { adicionarAoHistorico } from "./historico";
 import HistoricoModal from "./HistoricoModal";
 import "./GeradorCPFCNPJ.css";
 
@@ -143,7 +142,6 @@ function GeradorCPFCNPJ() {
             setCpf(valor);
             setCpfKey((prev) => prev + 1);
             setCopiadoCpf(false);
-            adicionarAoHistorico("cpf", valor);
         } catch (error) {
             console.error("Erro ao gerar CPF:", error);
         }
@@ -155,7 +153,6 @@ function GeradorCPFCNPJ() {
             setCnpj(valor);
             setCnpjKey((prev) => prev + 1);
             setCopiadoCnpj(false);
-            adicionarAoHistorico("cnpj", valor);
         } catch (error) {
             console.error("Erro ao gerar CNPJ:", error);
         }
@@ -1,4 +1,4 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import { HistoricoItem, formatarTimestamp, limparHistorico, obterHistorico } from "./historico";
 import { copiarParaClipboard } from "./gerador";
 
@@ -34,13 +34,20 @@ function HistoricoModal({ tipo, aberto, onFechar }: HistoricoModalProps) {
 
     const titulo = tipo === "cpf" ? "CPF" : "CNPJ";
 
-    function atualizar() {
-        setItens(obterHistorico(tipo));
-    }
+    useEffect(() => {
+        if (!aberto) return;
+        obterHistorico(tipo)
+            .then(setItens)
+            .catch((error) => console.error("Erro ao carregar histórico:", error));
+    }, [tipo, aberto]);
 
-    function handleLimpar() {
-        limparHistorico(tipo);
-        setItens([]);
+    async function handleLimpar() {
+        try {
+            await limparHistorico(tipo);
+            setItens(await obterHistorico(tipo));
+        } catch (error) {
+            console.error("Erro ao limpar histórico:", error);
+        }
     }
 
     async function handleCopiar(valor: string, idx: number) {
@@ -55,13 +62,6 @@ function HistoricoModal({ tipo, aberto, onFechar }: HistoricoModalProps) {
 
     if (!aberto) return null;
 
-    if (itens.length === 0 && aberto) {
-        const fresh = obterHistorico(tipo);
-        if (fresh.length !== itens.length) {
-            setTimeout(() => atualizar(), 0);
-        }
-    }
-
     return (
         <div className="historico-overlay" onClick={onFechar}>
             <div className="historico-modal" onClick={(e) => e.stopPropagation()}>
@@ -83,7 +83,7 @@ function HistoricoModal({ tipo, aberto, onFechar }: HistoricoModalProps) {
                     <>
                         <ul className="historico-lista">
                             {itens.map((item, idx) => (
-                                <li key={`${item.timestamp}-${idx}`} className="historico-item">
+                                <li key={item.id} className="historico-item">
                                     <div className="historico-item-info">
                                         <span className="historico-valor">{item.valor}</span>
                                         <span className="historico-data">{formatarTimestamp(item.timestamp)}</span>
@@ -1,11 +1,91 @@
 import { invoke } from "@tauri-apps/api/core";
 
-export async function gerarCpf(comMascara: boolean): Promise<string> {
-    return invoke<string>("gerar_cpf", { comMascara });
+export async function gerarCpf(comMascara: boolean, uf?: string, semente?: number): Promise<string> {
+    return invoke<string>("gerar_cpf", { comMascara, uf, semente });
 }
 
-export async function gerarCnpj(comMascara: boolean): Promise<string> {
-    return invoke<string>("gerar_cnpj", { comMascara });
+export interface OpcoesCnpj {
+    filial?: number;
+    filialAleatoria?: boolean;
+    semente?: number;
+}
+
+export async function gerarCnpj(
+    comMascara: boolean,
+    alfanumerico = false,
+    { filial, filialAleatoria, semente }: OpcoesCnpj = {},
+): Promise<string> {
+    return invoke<string>("gerar_cnpj", { comMascara, alfanumerico, filial, filialAleatoria, semente });
+}
+
+export interface FamiliaCnpj {
+    raiz: string;
+    matriz: string;
+    filiais: string[];
+}
+
+export async function gerarFamiliaCnpj(
+    comMascara: boolean,
+    quantidadeFiliais: number,
+    alfanumerico = false,
+    semente?: number,
+): Promise<FamiliaCnpj> {
+    return invoke<FamiliaCnpj>("gerar_familia_cnpj", { comMascara, alfanumerico, quantidadeFiliais, semente });
+}
+
+export async function mesmaRaizCnpj(cnpjA: string, cnpjB: string): Promise<boolean> {
+    return invoke<boolean>("mesma_raiz_cnpj", { cnpjA, cnpjB });
+}
+
+export type FormatoExportacao = "csv" | "json" | "ndjson" | "sql";
+
+export interface OpcoesLote {
+    tipo: "cpf" | "cnpj";
+    quantidade: number;
+    formato: FormatoExportacao;
+    caminho: string;
+    comMascara?: boolean;
+    semDuplicatas?: boolean;
+    alfanumerico?: boolean;
+    uf?: string;
+    semente?: number;
+    tabela?: string;
+    coluna?: string;
+}
+
+export interface ResultadoLote {
+    caminho: string;
+    quantidade: number;
+    formato: FormatoExportacao;
+}
+
+export async function gerarDocumentosEmLote(opcoes: OpcoesLote): Promise<ResultadoLote> {
+    return invoke<ResultadoLote>("gerar_documentos_em_lote", { opcoes });
+}
+
+export type DefeitoDocumento =
+    | "wrongFirstCheckDigit"
+    | "wrongSecondCheckDigit"
+    | "repeatedDigits"
+    | "tooShort"
+    | "tooLong"
+    | "invalidMask"
+    | "lettersMixedIn";
+
+export interface DocumentoInvalido {
+    tipo: "cpf" | "cnpj";
+    valor: string;
+    defeito: DefeitoDocumento;
+    expectedFailure: string;
+}
+
+export async function gerarDocumentosInvalidos(
+    tipo: "cpf" | "cnpj",
+    comMascara: boolean,
+    defeitos?: DefeitoDocumento[],
+    semente?: number,
+): Promise<DocumentoInvalido[]> {
+    return invoke<DocumentoInvalido[]>("gerar_documentos_invalidos", { tipo, defeitos, comMascara, semente });
 }
 
 export async function copiarParaClipboard(texto: string): Promise<void> {
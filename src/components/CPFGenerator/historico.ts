@@ -1,37 +1,74 @@
+import { invoke } from "@tauri-apps/api/core";
+
+// Chaves do histórico antigo, mantido no localStorage antes de o Rust assumir a persistência.
 const STORAGE_KEY_CPF = "fd4d_historico_cpf";
 const STORAGE_KEY_CNPJ = "fd4d_historico_cnpj";
-const MAX_ITEMS = 10;
+
+export type TipoHistorico = "cpf" | "cnpj";
 
 export interface HistoricoItem {
+    id: string;
+    tipo: TipoHistorico;
     valor: string;
     timestamp: number;
+    tags: string[];
+    fixado: boolean;
 }
 
-function getStorageKey(tipo: "cpf" | "cnpj"): string {
+export interface ConfiguracaoHistorico {
+    maxEntradas: number | null;
+}
+
+function getStorageKey(tipo: TipoHistorico): string {
     return tipo === "cpf" ? STORAGE_KEY_CPF : STORAGE_KEY_CNPJ;
 }
 
-export function obterHistorico(tipo: "cpf" | "cnpj"): HistoricoItem[] {
+// Move uma única vez o histórico do localStorage para o armazenamento do Rust.
+async function migrarLocalStorage(tipo: TipoHistorico): Promise<void> {
+    const raw = localStorage.getItem(getStorageKey(tipo));
+    if (!raw) return;
     try {
-        const raw = localStorage.getItem(getStorageKey(tipo));
-        if (!raw) return [];
-        return JSON.parse(raw) as HistoricoItem[];
-    } catch {
-        return [];
+        const itens = (JSON.parse(raw) as { valor: string; timestamp: number }[])
+            .map(({ valor, timestamp }) => ({ tipo, valor, timestamp }));
+        await invoke<number>("importar_historico", { itens });
+    } catch (error) {
+        console.error("Erro ao migrar histórico:", error);
+        return;
     }
+    localStorage.removeItem(getStorageKey(tipo));
 }
 
-export function adicionarAoHistorico(tipo: "cpf" | "cnpj", valor: string): void {
-    const historico = obterHistorico(tipo);
-    historico.unshift({ valor, timestamp: Date.now() });
-    if (historico.length > MAX_ITEMS) {
-        historico.length = MAX_ITEMS;
-    }
-    localStorage.setItem(getStorageKey(tipo), JSON.stringify(historico));
+export async function obterHistorico(tipo?: TipoHistorico, busca?: string, tag?: string): Promise<HistoricoItem[]> {
+    if (tipo) await migrarLocalStorage(tipo);
+    return invoke<HistoricoItem[]>("listar_historico", { tipo, busca, tag });
 }
 
-export function limparHistorico(tipo: "cpf" | "cnpj"): void {
-    localStorage.removeItem(getStorageKey(tipo));
+export async function marcarHistorico(id: string, tags: string[]): Promise<HistoricoItem> {
+    return invoke<HistoricoItem>("marcar_historico", { id, tags });
+}
+
+export async function fixarHistorico(id: string, fixado: boolean): Promise<HistoricoItem> {
+    return invoke<HistoricoItem>("fixar_historico", { id, fixado });
+}
+
+export async function removerDoHistorico(id: string): Promise<void> {
+    return invoke<void>("remover_historico", { id });
+}
+
+export async function limparHistorico(tipo?: TipoHistorico): Promise<void> {
+    return invoke<void>("limpar_historico", { tipo });
+}
+
+export async function obterConfiguracaoHistorico(): Promise<ConfiguracaoHistorico> {
+    return invoke<ConfiguracaoHistorico>("obter_configuracao_historico");
+}
+
+export async function configurarHistorico(maxEntradas: number | null): Promise<ConfiguracaoHistorico> {
+    return invoke<ConfiguracaoHistorico>("configurar_historico", { maxEntradas });
+}
+
+export async function exportarHistorico(caminho: string, formato: "csv" | "json", tipo?: TipoHistorico): Promise<number> {
+    return invoke<number>("exportar_historico", { caminho, formato, tipo });
 }
 
 export function formatarTimestamp(timestamp: number): string {
@@ -1,10 +1,9 @@
-import { useState } from "react";
+import { useRef, useState } from "react";
 import { BorderBeam } from "../ui/border-beam";
 import { ShimmerButton } from "../ui/shimmer-button";
 import {
     type ResultadoValidacao,
-    formatarCpfInput,
-    formatarCnpjInput,
+    formatarDocumento,
     validarCpf,
     validarCnpj,
 } from "./validador";
@@ -98,15 +97,26 @@ function ValidadorCPFCNPJ() {
     const [cnpjMsg, setCnpjMsg] = useState("");
     const [validandoCpf, setValidandoCpf] = useState(false);
     const [validandoCnpj, setValidandoCnpj] = useState(false);
+    const ultimaEdicaoCpf = useRef(0);
+    const ultimaEdicaoCnpj = useRef(0);
 
-    function handleCpfChange(e: React.ChangeEvent<HTMLInputElement>) {
-        setCpfInput(formatarCpfInput(e.target.value));
+    async function handleCpfChange(e: React.ChangeEvent<HTMLInputElement>) {
+        const valor = e.target.value;
+        const edicao = ++ultimaEdicaoCpf.current;
+        setCpfInput(valor);
         if (cpfRes !== "idle") { setCpfRes("idle"); setCpfMsg(""); }
+        const formatado = await formatarDocumento(valor, "cpf");
+        // Descarta respostas de teclas antigas que chegarem depois da mais recente.
+        if (edicao === ultimaEdicaoCpf.current) setCpfInput(formatado.valor);
     }
 
-    function handleCnpjChange(e: React.ChangeEvent<HTMLInputElement>) {
-        setCnpjInput(formatarCnpjInput(e.target.value));
+    async function handleCnpjChange(e: React.ChangeEvent<HTMLInputElement>) {
+        const valor = e.target.value;
+        const edicao = ++ultimaEdicaoCnpj.current;
+        setCnpjInput(valor);
         if (cnpjRes !== "idle") { setCnpjRes("idle"); setCnpjMsg(""); }
+        const formatado = await formatarDocumento(valor, "cnpj");
+        if (edicao === ultimaEdicaoCnpj.current) setCnpjInput(formatado.valor);
     }
 
     async function handleValidarCpf() {
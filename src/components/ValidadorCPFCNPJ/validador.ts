@@ -2,40 +2,90 @@ import { invoke } from "@tauri-apps/api/core";
 
 export type ResultadoValidacao = "idle" | "valido" | "invalido" | "erro";
 
-export function formatarCpfInput(valor: string): string {
-    const n = valor.replace(/\D/g, "").slice(0, 11);
-    if (n.length <= 3) return n;
-    if (n.length <= 6) return `${n.slice(0, 3)}.${n.slice(3)}`;
-    if (n.length <= 9) return `${n.slice(0, 3)}.${n.slice(3, 6)}.${n.slice(6)}`;
-    return `${n.slice(0, 3)}.${n.slice(3, 6)}.${n.slice(6, 9)}-${n.slice(9)}`;
+export type TipoDocumento = "cpf" | "cnpj";
+
+export interface DocumentoFormatado {
+    tipo: TipoDocumento;
+    valor: string;
+    normalizado: string;
+    completo: boolean;
+}
+
+// A máscara é aplicada no Rust, mesma implementação usada pelos geradores.
+export async function formatarDocumento(documento: string, tipo?: TipoDocumento): Promise<DocumentoFormatado> {
+    return invoke<DocumentoFormatado>("formatar_documento", { documento, tipo });
 }
 
-export function formatarCnpjInput(valor: string): string {
-    const n = valor.replace(/\D/g, "").slice(0, 14);
-    if (n.length <= 2) return n;
-    if (n.length <= 5) return `${n.slice(0, 2)}.${n.slice(2)}`;
-    if (n.length <= 8) return `${n.slice(0, 2)}.${n.slice(2, 5)}.${n.slice(5)}`;
-    if (n.length <= 12) return `${n.slice(0, 2)}.${n.slice(2, 5)}.${n.slice(5, 8)}/${n.slice(8)}`;
-    return `${n.slice(0, 2)}.${n.slice(2, 5)}.${n.slice(5, 8)}/${n.slice(8, 12)}-${n.slice(12)}`;
+export async function normalizarDocumento(documento: string, tipo?: TipoDocumento): Promise<string> {
+    return invoke<string>("normalizar_documento", { documento, tipo });
+}
+
+export type FalhaValidacao =
+    | "wrongLength"
+    | "invalidCharacters"
+    | "invalidMask"
+    | "invalidPrefix"
+    | "invalidUfCode"
+    | "repeatedDigits"
+    | "firstCheckDigitMismatch"
+    | "secondCheckDigitMismatch"
+    | "fieldCheckDigitMismatch"
+    | "checksumMismatch";
+
+export interface ValidationReport {
+    valid: boolean;
+    failure: FalhaValidacao | null;
+    expectedCheckDigits: string | null;
+    suppliedCheckDigits: string | null;
+    normalized: string;
+    masked: string | null;
+    wasMasked: boolean;
+    likelyUfs: string[];
 }
 
 export interface ResultadoValidar {
     resultado: ResultadoValidacao;
     mensagem: string;
+    relatorio: ValidationReport;
 }
 
-export async function validarCpf(cpfInput: string): Promise<ResultadoValidar> {
-    const valido = await invoke<boolean>("validar_cpf", { cpfInput });
+function descreverFalha(documento: "CPF" | "CNPJ", relatorio: ValidationReport): string {
+    const esperado = relatorio.expectedCheckDigits ?? "";
+    const informado = relatorio.suppliedCheckDigits ?? "";
+    switch (relatorio.failure) {
+        case "wrongLength":
+            return `${documento} inválido: deve conter ${documento === "CPF" ? 11 : 14} caracteres (informados ${relatorio.normalized.length}).`;
+        case "invalidCharacters":
+            return documento === "CPF"
+                ? "CPF inválido: deve conter apenas dígitos."
+                : "CNPJ inválido: os dígitos verificadores devem ser numéricos.";
+        case "invalidMask":
+            return `${documento} inválido: use só dígitos e os separadores . - / (${relatorio.masked ?? ""}).`;
+        case "repeatedDigits":
+            return `${documento} inválido: todos os dígitos são iguais.`;
+        case "firstCheckDigitMismatch":
+            return `${documento} inválido: primeiro dígito verificador esperado ${esperado[0]}, informado ${informado[0]}.`;
+        case "secondCheckDigitMismatch":
+            return `${documento} inválido: segundo dígito verificador esperado ${esperado[1]}, informado ${informado[1]}.`;
+        default:
+            return `${documento} inválido!`;
+    }
+}
+
+function montarResultado(documento: "CPF" | "CNPJ", relatorio: ValidationReport): ResultadoValidar {
     return {
-        resultado: valido ? "valido" : "invalido",
-        mensagem: valido ? "CPF válido!" : "CPF inválido!",
+        resultado: relatorio.valid ? "valido" : "invalido",
+        mensagem: relatorio.valid ? `${documento} válido!` : descreverFalha(documento, relatorio),
+        relatorio,
     };
 }
 
+export async function validarCpf(cpfInput: string): Promise<ResultadoValidar> {
+    const relatorio = await invoke<ValidationReport>("validar_cpf", { cpfInput });
+    return montarResultado("CPF", relatorio);
+}
+
 export async function validarCnpj(cnpjInput: string): Promise<ResultadoValidar> {
-    const valido = await invoke<boolean>("validar_cnpj", { cnpjInput });
-    return {
-        resultado: valido ? "valido" : "invalido",
-        mensagem: valido ? "CNPJ válido!" : "CNPJ inválido!",
-    };
+    const relatorio = await invoke<ValidationReport>("validar_cnpj", { cnpjInput });
+    return montarResultado("CNPJ", relatorio);
 }
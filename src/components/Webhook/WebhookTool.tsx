@@ -1,4 +1,5 @@
-import { useState, useEffect, useRef, useCallback } from "react";
+import { useState, useEffect, useRef } from "react";
+import { ask } from "@tauri-apps/plugin-dialog";
 import { BorderBeam } from "../ui/border-beam";
 import { ShimmerButton } from "../ui/shimmer-button";
 import RequestCard from "./RequestCard";
@@ -8,11 +9,14 @@ import {
     AUTH_OPTIONS,
     startServer,
     stopServer,
-    getServerRequests,
+    listServerRequests,
     clearServerRequests,
     sendWebhookRequest,
     buildAuthHeaders,
     formatTimestamp,
+    onWebhookRequest,
+    onWebhookLifecycle,
+    toReceivedEntry,
 } from "./logic";
 import "./Webhook.css";
 
@@ -50,42 +54,40 @@ export default function WebhookTool() {
 
     /* ===== Lista de Requests ===== */
     const [requests, setRequests] = useState<RequestEntry[]>([]);
-    const pollingRef = useRef<ReturnType<typeof setInterval> | null>(null);
     const listRef = useRef<HTMLDivElement>(null);
 
-    /* ===== Polling das requests recebidas ===== */
-    const pollRequests = useCallback(async () => {
-        if (!serverRunning) return;
-        try {
-            const received = await getServerRequests();
-            setRequests(prev => {
-                const sentOnly = prev.filter(r => r.direction === "sent");
-                const receivedEntries: RequestEntry[] = received.map(r => ({
-                    id: r.id,
-                    timestamp: r.timestamp,
-                    direction: "received",
-                    method: r.method,
-                    url: r.path,
-                    headers: r.headers,
-                    body: r.body,
-                    statusCode: r.statusCode,
-                    errorReason: r.errorReason,
-                }));
-                return [...sentOnly, ...receivedEntries].sort(
-                    (a, b) => b.timestamp.localeCompare(a.timestamp)
-                );
-            });
-        } catch { /* silently ignore polling errors */ }
-    }, [serverRunning]);
-
+    /* ===== Requests recebidas e estado do servidor via eventos ===== */
     useEffect(() => {
-        if (serverRunning) {
-            pollingRef.current = setInterval(pollRequests, 800);
-        }
+        listServerRequests({ limit: 100 })
+            .then(page => {
+                setRequests(prev => {
+                    const known = new Set(prev.map(r => r.id));
+                    const stored = page.items
+                        .filter(r => !known.has(r.id))
+                        .map(toReceivedEntry);
+                    return [...prev, ...stored];
+                });
+            })
+            .catch(error => console.error("Erro ao carregar capturas:", error));
+
+        const unlisteners = [
+            onWebhookRequest(r => {
+                setRequests(prev => [toReceivedEntry(r), ...prev]);
+            }),
+            onWebhookLifecycle(event => {
+                if (event.type === "crashed") {
+                    console.error("Servidor de webhook caiu:", event.error);
+                }
+                if (event.type === "crashed" || event.type === "stopped") {
+                    setServerRunning(false);
+                    setServerUrl("");
+                }
+            }),
+        ];
         return () => {
-            if (pollingRef.current) clearInterval(pollingRef.current);
+            unlisteners.forEach(p => p.then(unlisten => unlisten()));
         };
-    }, [serverRunning, pollRequests]);
+    }, []);
 
     /* ===== Atualiza URL alvo quando server inicia ===== */
     useEffect(() => {
@@ -155,11 +157,15 @@ export default function WebhookTool() {
         }
     }
 
+    /* A lista mostra capturas de todas as sessões, então limpar apaga todas do disco */
     async function handleClearRequests() {
+        const confirmed = await ask(
+            "Apagar todas as requests capturadas, inclusive as de sessões anteriores?",
+            { title: "Webhook Tester", kind: "warning" }
+        );
+        if (!confirmed) return;
         setRequests([]);
-        if (serverRunning) {
-            try { await clearServerRequests(); } catch { /* ignore */ }
-        }
+        try { await clearServerRequests(); } catch { /* ignore */ }
     }
 
     return (
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 /* ===== Tipos ===== */
 export type AuthMethod = "none" | "bearer" | "api-key" | "basic";
@@ -8,12 +9,92 @@ export interface WebhookRequest {
     timestamp: string;
     method: string;
     path: string;
+    query: Record<string, string>;
+    route: string | null;
     headers: Record<string, string>;
     body: string;
     statusCode: number;
     errorReason: string | null;
+    matchedRule: string | null;
+    faults: InjectedFault[];
 }
 
+export interface FaultConfig {
+    delayMs?: number | null;
+    delayMaxMs?: number | null;
+    errorRate?: number | null;
+    errorStatus?: number | null;
+    hang?: boolean;
+    dropConnection?: boolean;
+    failFirst?: number | null;
+}
+
+export type InjectedFault =
+    | { type: "delay"; ms: number }
+    | { type: "error"; statusCode: number }
+    | { type: "failFirst"; attempt: number; of: number; statusCode: number }
+    | { type: "hang" }
+    | { type: "dropConnection" };
+
+export interface ResponseRule {
+    id: string;
+    name: string;
+    enabled: boolean;
+    method: string | null;
+    path: string | null;
+    header: { name: string; value: string | null } | null;
+    bodyField: { field: string; value: string | null } | null;
+    statusCode: number;
+    headers: Record<string, string>;
+    body: string;
+    contentType: string | null;
+    faults: FaultConfig | null;
+}
+
+export interface StoredWebhookRequest extends WebhookRequest {
+    sessionId: string | null;
+    receivedAt: number;
+}
+
+export interface WebhookSession {
+    id: string;
+    url: string;
+    startedAt: number;
+    endedAt: number | null;
+    requestCount: number;
+}
+
+export interface WebhookQuery {
+    sessionId?: string;
+    search?: string;
+    method?: string;
+    path?: string;
+    statusMin?: number;
+    statusMax?: number;
+    since?: number;
+    until?: number;
+    offset?: number;
+    limit?: number;
+}
+
+export interface WebhookPage {
+    items: StoredWebhookRequest[];
+    total: number;
+    offset: number;
+    limit: number;
+}
+
+export interface WebhookRetention {
+    maxRequests: number | null;
+    maxAgeDays: number | null;
+}
+
+export type WebhookLifecycleEvent =
+    | { type: "started"; url: string }
+    | { type: "stopped" }
+    | { type: "bindError"; error: string }
+    | { type: "crashed"; error: string };
+
 export interface SendWebhookResponse {
     statusCode: number;
     headers: Record<string, string>;
@@ -54,12 +135,48 @@ export async function stopServer(): Promise<void> {
     await invoke("stop_webhook_server");
 }
 
-export async function getServerRequests(): Promise<WebhookRequest[]> {
-    return invoke<WebhookRequest[]>("get_webhook_requests");
+export async function listServerRequests(query: WebhookQuery = {}): Promise<WebhookPage> {
+    return invoke<WebhookPage>("list_webhook_requests", { query });
+}
+
+export async function listServerSessions(): Promise<WebhookSession[]> {
+    return invoke<WebhookSession[]>("list_webhook_sessions");
 }
 
-export async function clearServerRequests(): Promise<void> {
-    await invoke("clear_webhook_requests");
+export async function clearServerRequests(sessionId?: string): Promise<void> {
+    await invoke("clear_webhook_requests", { sessionId: sessionId ?? null });
+}
+
+export async function getRetention(): Promise<WebhookRetention> {
+    return invoke<WebhookRetention>("get_webhook_retention");
+}
+
+export async function setRetention(retention: WebhookRetention): Promise<WebhookRetention> {
+    return invoke<WebhookRetention>("set_webhook_retention", { retention });
+}
+
+export async function listRules(): Promise<ResponseRule[]> {
+    return invoke<ResponseRule[]>("list_webhook_rules");
+}
+
+export async function saveRule(rule: ResponseRule): Promise<ResponseRule> {
+    return invoke<ResponseRule>("save_webhook_rule", { rule });
+}
+
+export async function deleteRule(id: string): Promise<void> {
+    await invoke("delete_webhook_rule", { id });
+}
+
+export async function setRules(rules: ResponseRule[]): Promise<ResponseRule[]> {
+    return invoke<ResponseRule[]>("set_webhook_rules", { rules });
+}
+
+export async function getFaults(): Promise<FaultConfig> {
+    return invoke<FaultConfig>("get_webhook_faults");
+}
+
+export async function setFaults(faults: FaultConfig): Promise<FaultConfig> {
+    return invoke<FaultConfig>("set_webhook_faults", { faults });
 }
 
 export async function sendWebhookRequest(
@@ -76,6 +193,19 @@ export async function sendWebhookRequest(
     });
 }
 
+/* ===== Eventos Tauri ===== */
+export function onWebhookRequest(
+    handler: (request: WebhookRequest) => void
+): Promise<UnlistenFn> {
+    return listen<WebhookRequest>("webhook://request", e => handler(e.payload));
+}
+
+export function onWebhookLifecycle(
+    handler: (event: WebhookLifecycleEvent) => void
+): Promise<UnlistenFn> {
+    return listen<WebhookLifecycleEvent>("webhook://lifecycle", e => handler(e.payload));
+}
+
 /* ===== Helpers ===== */
 export function buildAuthHeaders(
     method: AuthMethod,
@@ -103,6 +233,25 @@ export function formatTimestamp(): string {
     });
 }
 
+export function buildRequestUrl(path: string, query: Record<string, string>): string {
+    const params = new URLSearchParams(query).toString();
+    return params ? `${path}?${params}` : path;
+}
+
+export function toReceivedEntry(r: WebhookRequest): RequestEntry {
+    return {
+        id: r.id,
+        timestamp: r.timestamp,
+        direction: "received",
+        method: r.method,
+        url: buildRequestUrl(r.path, r.query),
+        headers: r.headers,
+        body: r.body,
+        statusCode: r.statusCode,
+        errorReason: r.errorReason,
+    };
+}
+
 export function truncateBody(body: string, maxLen = 120): string {
     if (!body) return "(vazio)";
     return body.length > maxLen ? body.slice(0, maxLen) + "…" : body;